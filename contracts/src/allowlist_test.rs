@@ -1,17 +1,7 @@
 #![cfg(test)]
+use crate::test_utils::create_token_contract;
 use crate::{StellarStreamContract, StellarStreamContractClient};
-use soroban_sdk::{
-    testutils::Address as _,
-    token::{StellarAssetClient, TokenClient},
-    Address, Env,
-};
-
-fn create_token_contract<'a>(env: &Env, admin: &Address) -> (Address, TokenClient<'a>) {
-    let contract_id = env
-        .register_stellar_asset_contract_v2(admin.clone())
-        .address();
-    (contract_id.clone(), TokenClient::new(env, &contract_id))
-}
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env};
 
 #[test]
 fn test_allowlist_disabled_by_default() {
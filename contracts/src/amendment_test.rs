@@ -0,0 +1,235 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::create_token_contract;
+use crate::types::{AmendmentTerms, CurveType, StreamAmendedEvent};
+use crate::{StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, IntoVal,
+};
+
+/// A linear stream of 1000 from 0 to 1000, with 10_000 minted to the sender
+fn setup_test(
+    env: &Env,
+) -> (
+    StellarStreamContractClient<'_>,
+    u64,
+    Address,
+    Address,
+    TokenClient<'_>,
+) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+    let receiver = Address::generate(env);
+
+    let (token_address, token_client) = create_token_contract(env, &admin);
+    StellarAssetClient::new(env, &token_address).mint(&sender, &10_000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_address,
+        &1000,
+        &0,
+        &1000,
+        &CurveType::Linear,
+        &false,
+    );
+
+    (client, stream_id, sender, receiver, token_client)
+}
+
+fn terms(receiver: &Address, total_amount: i128, cliff_time: u64, end_time: u64) -> AmendmentTerms {
+    AmendmentTerms {
+        total_amount,
+        end_time,
+        cliff_time,
+        curve_type: CurveType::Linear,
+        receiver: receiver.clone(),
+    }
+}
+
+#[test]
+fn test_amendment_keeps_unlocked_amount() {
+    let env = Env::default();
+    let (client, stream_id, sender, receiver, token_client) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 400);
+
+    // The sender escrows the extra 500 with the proposal
+    let new_terms = terms(&receiver, 1500, 400, 1400);
+    client.propose_amendment(&stream_id, &sender, &new_terms);
+    assert_eq!(token_client.balance(&sender), 8500);
+    assert_eq!(client.get_amendment(&stream_id).unwrap().escrowed, 500);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    client.accept_amendment(&stream_id, &receiver, &new_terms);
+    assert_eq!(client.get_amendment(&stream_id), None);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.total_amount, 1500);
+    assert_eq!(stream.carried_amount, 500);
+    assert_eq!(stream.end_time, 1400);
+
+    // 100 accrued between the proposal and acceptance is still claimable,
+    // and the other 1000 runs linearly over the 900s left
+    assert_eq!(client.withdraw(&stream_id, &receiver), 100);
+    env.ledger().with_mut(|li| li.timestamp = 950);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 500);
+    env.ledger().with_mut(|li| li.timestamp = 1400);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 500);
+    assert_eq!(token_client.balance(&receiver), 1500);
+}
+
+#[test]
+fn test_lower_total_is_refunded_to_sender() {
+    let env = Env::default();
+    let (client, stream_id, sender, receiver, token_client) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    let new_terms = terms(&receiver, 600, 200, 1000);
+    client.propose_amendment(&stream_id, &receiver, &new_terms);
+    client.accept_amendment(&stream_id, &sender, &new_terms);
+
+    assert_eq!(token_client.balance(&sender), 9400);
+    assert_eq!(client.get_stream(&stream_id).total_amount, 600);
+
+    // 200 unlocked before, then 400 over the remaining 800s
+    env.ledger().with_mut(|li| li.timestamp = 600);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 400);
+}
+
+#[test]
+fn test_sender_accepting_higher_total_pays_difference() {
+    let env = Env::default();
+    let (client, stream_id, sender, receiver, token_client) = setup_test(&env);
+
+    let new_terms = terms(&receiver, 1200, 0, 1000);
+    client.propose_amendment(&stream_id, &receiver, &new_terms);
+    assert_eq!(token_client.balance(&sender), 9000);
+
+    client.accept_amendment(&stream_id, &sender, &new_terms);
+    assert_eq!(token_client.balance(&sender), 8800);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 1200);
+}
+
+#[test]
+fn test_accepted_terms_must_match_proposal() {
+    let env = Env::default();
+    let (client, stream_id, sender, receiver, _token_client) = setup_test(&env);
+
+    client.propose_amendment(&stream_id, &receiver, &terms(&receiver, 1000, 0, 500));
+
+    let result = client.try_accept_amendment(&stream_id, &sender, &terms(&receiver, 1000, 0, 400));
+    assert_eq!(result, Err(Ok(Error::AmendmentMismatch)));
+}
+
+#[test]
+fn test_proposer_cannot_accept_own_amendment() {
+    let env = Env::default();
+    let (client, stream_id, sender, receiver, _token_client) = setup_test(&env);
+
+    let new_terms = terms(&receiver, 1000, 0, 500);
+    client.propose_amendment(&stream_id, &sender, &new_terms);
+
+    let result = client.try_accept_amendment(&stream_id, &sender, &new_terms);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_total_cannot_drop_below_unlocked() {
+    let env = Env::default();
+    let (client, stream_id, sender, receiver, _token_client) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    let new_terms = terms(&receiver, 350, 300, 1000);
+    client.propose_amendment(&stream_id, &sender, &new_terms);
+
+    // More has unlocked by the time the receiver gets round to it
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    let result = client.try_accept_amendment(&stream_id, &receiver, &new_terms);
+    assert_eq!(result, Err(Ok(Error::InvalidAmendment)));
+
+    let result =
+        client.try_propose_amendment(&stream_id, &sender, &terms(&receiver, 350, 400, 1000));
+    assert_eq!(result, Err(Ok(Error::InvalidAmendment)));
+}
+
+#[test]
+fn test_reject_returns_escrow() {
+    let env = Env::default();
+    let (client, stream_id, sender, receiver, token_client) = setup_test(&env);
+
+    client.propose_amendment(&stream_id, &sender, &terms(&receiver, 1500, 0, 1000));
+    assert_eq!(token_client.balance(&sender), 8500);
+
+    client.reject_amendment(&stream_id, &receiver);
+    assert_eq!(token_client.balance(&sender), 9000);
+    assert_eq!(client.get_amendment(&stream_id), None);
+
+    let result = client.try_reject_amendment(&stream_id, &receiver);
+    assert_eq!(result, Err(Ok(Error::NoPendingAmendment)));
+}
+
+#[test]
+fn test_new_proposal_replaces_pending_one() {
+    let env = Env::default();
+    let (client, stream_id, sender, receiver, token_client) = setup_test(&env);
+
+    client.propose_amendment(&stream_id, &sender, &terms(&receiver, 1500, 0, 1000));
+    client.propose_amendment(&stream_id, &sender, &terms(&receiver, 1100, 0, 1000));
+
+    assert_eq!(token_client.balance(&sender), 8900);
+    assert_eq!(client.get_amendment(&stream_id).unwrap().escrowed, 100);
+}
+
+#[test]
+fn test_cancel_returns_escrow() {
+    let env = Env::default();
+    let (client, stream_id, sender, receiver, token_client) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 250);
+    client.propose_amendment(&stream_id, &sender, &terms(&receiver, 1500, 250, 1000));
+    client.cancel(&stream_id, &sender);
+
+    assert_eq!(token_client.balance(&receiver), 250);
+    assert_eq!(token_client.balance(&sender), 9750);
+    assert_eq!(client.get_amendment(&stream_id), None);
+}
+
+#[test]
+fn test_amendment_moves_stream_to_new_receiver() {
+    let env = Env::default();
+    let (client, stream_id, sender, receiver, _token_client) = setup_test(&env);
+    let new_receiver = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    let new_terms = terms(&new_receiver, 1000, 100, 1000);
+    client.propose_amendment(&stream_id, &receiver, &new_terms);
+    client.accept_amendment(&stream_id, &sender, &new_terms);
+
+    let events = env.events().all();
+    let event = events.last().unwrap();
+    assert_eq!(
+        event.1,
+        (symbol_short!("amended"), stream_id).into_val(&env)
+    );
+    let amended: StreamAmendedEvent = event.2.into_val(&env);
+    assert_eq!(amended.carried_amount, 100);
+    assert_eq!(amended.accepted_by, sender);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.receiver, new_receiver);
+    assert_eq!(stream.receipt_owner, new_receiver);
+    assert_eq!(client.withdraw(&stream_id, &new_receiver), 100);
+}
@@ -0,0 +1,225 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::create_token_contract;
+use crate::types::{CurveType, StreamRequest, MAX_BATCH_SIZE};
+use crate::{StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    Address, BytesN, Env, Vec,
+};
+
+fn request(env: &Env, amount: i128, cliff_time: u64) -> StreamRequest {
+    StreamRequest {
+        receiver: Address::generate(env),
+        amount,
+        start_time: 0,
+        cliff_time,
+        end_time: 1000,
+        interest_strategy: 2,
+        vault_address: None,
+        metadata: None,
+    }
+}
+
+#[test]
+fn test_batch_creates_all_streams_with_one_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let (token_address, token_client) = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token_address).mint(&sender, &5000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+
+    let metadata = BytesN::from_array(&env, &[7; 32]);
+    let mut requests = Vec::new(&env);
+    requests.push_back(request(&env, 1000, 100));
+    requests.push_back(request(&env, 1500, 0));
+    requests.push_back(StreamRequest {
+        interest_strategy: 1,
+        metadata: Some(metadata.clone()),
+        ..request(&env, 500, 500)
+    });
+
+    let stream_ids = client.create_streams_batch(&sender, &token_address, &requests);
+
+    assert_eq!(stream_ids.len(), 3);
+    assert_eq!(token_client.balance(&contract_id), 3000);
+    assert_eq!(token_client.balance(&sender), 2000);
+
+    for (stream_id, req) in stream_ids.iter().zip(requests.iter()) {
+        let stream = client.get_stream(&stream_id);
+        assert_eq!(stream.sender, sender);
+        assert_eq!(stream.receiver, req.receiver);
+        assert_eq!(stream.total_amount, req.amount);
+        assert_eq!(stream.cliff_time, req.cliff_time);
+        assert_eq!(stream.interest_strategy, req.interest_strategy);
+        assert_eq!(stream.metadata, req.metadata);
+        assert_eq!(stream.curve_type, CurveType::Linear);
+        assert!(!stream.is_soulbound);
+    }
+
+    // Cliffs are applied per stream
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    let first = requests.get(0).unwrap();
+    let result = client.try_withdraw(&stream_ids.get(0).unwrap(), &first.receiver);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+
+    let second = requests.get(1).unwrap();
+    assert_eq!(
+        client.withdraw(&stream_ids.get(1).unwrap(), &second.receiver),
+        75
+    );
+}
+
+#[test]
+fn test_zero_cliff_means_no_cliff() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let (token_address, _) = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token_address).mint(&sender, &1000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+
+    let requests = Vec::from_array(
+        &env,
+        [StreamRequest {
+            start_time: 100,
+            ..request(&env, 1000, 0)
+        }],
+    );
+    let stream_ids = client.create_streams_batch(&sender, &token_address, &requests);
+    assert_eq!(
+        client.get_stream(&stream_ids.get(0).unwrap()).cliff_time,
+        100
+    );
+}
+
+#[test]
+fn test_batch_is_atomic_when_one_request_is_invalid() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let (token_address, token_client) = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token_address).mint(&sender, &5000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+
+    let mut requests = Vec::new(&env);
+    requests.push_back(request(&env, 1000, 100));
+    // Cliff after end_time
+    requests.push_back(request(&env, 1000, 2000));
+
+    let result = client.try_create_streams_batch(&sender, &token_address, &requests);
+    assert_eq!(result, Err(Ok(Error::InvalidTimeRange)));
+
+    assert_eq!(token_client.balance(&sender), 5000);
+    assert_eq!(
+        client.try_get_stream(&0).err(),
+        Some(Ok(Error::StreamNotFound))
+    );
+}
+
+#[test]
+fn test_batch_rejects_invalid_interest_strategy() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let (token_address, _) = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token_address).mint(&sender, &5000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+
+    let mut requests = Vec::new(&env);
+    requests.push_back(StreamRequest {
+        interest_strategy: 99,
+        ..request(&env, 1000, 0)
+    });
+
+    let result = client.try_create_streams_batch(&sender, &token_address, &requests);
+    assert_eq!(result, Err(Ok(Error::InvalidInterestStrategy)));
+}
+
+#[test]
+fn test_empty_batch_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let (token_address, _) = create_token_contract(&env, &admin);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+
+    let requests: Vec<StreamRequest> = Vec::new(&env);
+    let result = client.try_create_streams_batch(&sender, &token_address, &requests);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+}
+
+#[test]
+fn test_batch_size_is_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let (token_address, token_client) = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token_address).mint(&sender, &100_000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+
+    let mut requests = Vec::new(&env);
+    for _ in 0..MAX_BATCH_SIZE {
+        requests.push_back(request(&env, 1000, 0));
+    }
+    let stream_ids = client.create_streams_batch(&sender, &token_address, &requests);
+    assert_eq!(stream_ids.len(), MAX_BATCH_SIZE);
+
+    requests.push_back(request(&env, 1000, 0));
+    let result = client.try_create_streams_batch(&sender, &token_address, &requests);
+    assert_eq!(result, Err(Ok(Error::BatchTooLarge)));
+    assert_eq!(
+        token_client.balance(&contract_id),
+        1000 * MAX_BATCH_SIZE as i128
+    );
+}
+
+#[test]
+fn test_batch_rejects_restricted_receiver_before_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let (token_address, token_client) = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token_address).mint(&sender, &5000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    let mut requests = Vec::new(&env);
+    requests.push_back(request(&env, 1000, 0));
+    requests.push_back(request(&env, 1000, 0));
+    client.restrict_address(&admin, &requests.get(1).unwrap().receiver);
+
+    let result = client.try_create_streams_batch(&sender, &token_address, &requests);
+    assert_eq!(result, Err(Ok(Error::AddressRestricted)));
+    assert_eq!(token_client.balance(&sender), 5000);
+}
@@ -0,0 +1,173 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::setup_test;
+use crate::types::{CancelAuthority, CancellationPolicy, StreamOptions};
+use crate::StellarStreamContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+fn create_with_policy(
+    env: &Env,
+    client: &StellarStreamContractClient,
+    sender: &Address,
+    receiver: &Address,
+    token: &Address,
+    authority: CancelAuthority,
+    cancelable_until: Option<u64>,
+) -> u64 {
+    let options = StreamOptions {
+        cancellation: CancellationPolicy {
+            authority,
+            cancelable_until,
+        },
+        ..StreamOptions::new(env)
+    };
+    client.create_stream_with_options(sender, receiver, token, &1000, &0, &100, &options)
+}
+
+#[test]
+fn test_non_cancelable_stream() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+    let stream_id = create_with_policy(
+        &env,
+        &client,
+        &sender,
+        &receiver,
+        &token,
+        CancelAuthority::Nobody,
+        None,
+    );
+
+    assert_eq!(
+        client.get_stream(&stream_id).cancellation.authority,
+        CancelAuthority::Nobody
+    );
+    assert_eq!(
+        client.try_cancel(&stream_id, &sender),
+        Err(Ok(Error::CancellationNotAllowed))
+    );
+    assert_eq!(
+        client.try_cancel(&stream_id, &receiver),
+        Err(Ok(Error::CancellationNotAllowed))
+    );
+}
+
+#[test]
+fn test_sender_only_protects_payroll() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+    let stream_id = create_with_policy(
+        &env,
+        &client,
+        &sender,
+        &receiver,
+        &token,
+        CancelAuthority::SenderOnly,
+        None,
+    );
+
+    assert_eq!(
+        client.try_cancel(&stream_id, &receiver),
+        Err(Ok(Error::CancellationNotAllowed))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 40);
+    client.cancel(&stream_id, &sender);
+    assert_eq!(token_client.balance(&receiver), 400);
+    assert_eq!(token_client.balance(&sender), 9600);
+}
+
+#[test]
+fn test_receiver_only_prevents_rug() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+    let stream_id = create_with_policy(
+        &env,
+        &client,
+        &sender,
+        &receiver,
+        &token,
+        CancelAuthority::ReceiverOnly,
+        None,
+    );
+
+    assert_eq!(
+        client.try_cancel(&stream_id, &sender),
+        Err(Ok(Error::CancellationNotAllowed))
+    );
+
+    // The right to cancel follows the receipt
+    let buyer = Address::generate(&env);
+    client.transfer_receipt(&stream_id, &receiver, &buyer);
+    assert_eq!(
+        client.try_cancel(&stream_id, &receiver),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.cancel(&stream_id, &buyer);
+    assert!(client.get_stream(&stream_id).cancelled);
+}
+
+#[test]
+fn test_cancellation_window_closes() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+    let first = create_with_policy(
+        &env,
+        &client,
+        &sender,
+        &receiver,
+        &token,
+        CancelAuthority::Both,
+        Some(30),
+    );
+    let second = create_with_policy(
+        &env,
+        &client,
+        &sender,
+        &receiver,
+        &token,
+        CancelAuthority::Both,
+        Some(30),
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 29);
+    client.cancel(&first, &sender);
+
+    env.ledger().with_mut(|li| li.timestamp = 30);
+    assert_eq!(
+        client.try_cancel(&second, &sender),
+        Err(Ok(Error::CancellationNotAllowed))
+    );
+    assert_eq!(
+        client.try_cancel(&second, &receiver),
+        Err(Ok(Error::CancellationNotAllowed))
+    );
+}
+
+#[test]
+fn test_default_policy_allows_both_parties() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &100,
+        &crate::types::CurveType::Linear,
+        &false,
+    );
+
+    assert_eq!(
+        client.get_stream(&stream_id).cancellation,
+        CancellationPolicy {
+            authority: CancelAuthority::Both,
+            cancelable_until: None,
+        }
+    );
+    client.cancel(&stream_id, &receiver);
+}
@@ -1,4 +1,5 @@
 #![cfg(test)]
+use crate::test_utils::create_token_contract;
 use crate::{StellarStreamContract, StellarStreamContractClient};
 use soroban_sdk::{
     testutils::{Address as _, Ledger, LedgerInfo},
@@ -6,13 +7,6 @@ use soroban_sdk::{
     Address, Env,
 };
 
-fn create_token_contract<'a>(env: &Env, admin: &Address) -> (Address, TokenClient<'a>) {
-    let contract_id = env
-        .register_stellar_asset_contract_v2(admin.clone())
-        .address();
-    (contract_id.clone(), TokenClient::new(env, &contract_id))
-}
-
 fn set_compliance_officer_role(env: &Env, contract_id: &Address, officer: &Address) {
     env.as_contract(contract_id, || {
         env.storage().instance().set(
@@ -0,0 +1,157 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::setup_test;
+use crate::types::{CurveType, TopUpMode};
+use soroban_sdk::{testutils::Ledger, Env};
+
+#[test]
+fn test_nothing_withdrawable_before_cliff() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+
+    let stream_id = client.create_stream_with_cliff(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &100,
+        &400,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(client.get_stream(&stream_id).cliff_time, 100);
+
+    env.ledger().with_mut(|li| li.timestamp = 99);
+    let result = client.try_withdraw(&stream_id, &receiver);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+
+    // Everything accrued during the first 100 seconds unlocks at the cliff
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 250);
+    assert_eq!(token_client.balance(&receiver), 250);
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 750);
+}
+
+#[test]
+fn test_cliff_with_exponential_curve() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let stream_id = client.create_stream_with_cliff(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &50,
+        &100,
+        &CurveType::Exponential,
+        &false,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 49);
+    let result = client.try_withdraw(&stream_id, &receiver);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+
+    // 0.5^2 of the total has accrued by the cliff
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 250);
+}
+
+#[test]
+fn test_pause_delays_cliff() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let stream_id = client.create_stream_with_cliff(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &100,
+        &400,
+        &CurveType::Linear,
+        &false,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    client.pause_stream(&stream_id, &sender);
+    env.ledger().with_mut(|li| li.timestamp = 80);
+    client.unpause_stream(&stream_id, &sender);
+
+    // 30 seconds were paused, so the cliff is effectively reached at t=130
+    env.ledger().with_mut(|li| li.timestamp = 110);
+    let result = client.try_withdraw(&stream_id, &receiver);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+
+    env.ledger().with_mut(|li| li.timestamp = 130);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 250);
+}
+
+#[test]
+fn test_cliff_survives_top_up() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let stream_id = client.create_stream_with_cliff(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &100,
+        &200,
+        &CurveType::Linear,
+        &false,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    client.top_up_stream(&stream_id, &sender, &1000, &TopUpMode::ExtendDuration);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.cliff_time, 100);
+    assert_eq!(stream.end_time, 400);
+
+    env.ledger().with_mut(|li| li.timestamp = 99);
+    let result = client.try_withdraw(&stream_id, &receiver);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 500);
+}
+
+#[test]
+fn test_cliff_outside_stream_window_rejected() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let result = client.try_create_stream_with_cliff(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &100,
+        &50,
+        &400,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidTimeRange)));
+
+    let result = client.try_create_stream_with_cliff(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &100,
+        &401,
+        &400,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidTimeRange)));
+}
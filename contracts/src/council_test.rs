@@ -0,0 +1,339 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::storage::{self, TTL_EXTEND_TO, TTL_THRESHOLD};
+use crate::test_utils::create_token_contract;
+use crate::types::{
+    CouncilMember, DataKey, ProposalConfig, StreamOptions, COUNCIL_UPDATE_LIFETIME,
+};
+use crate::{StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Address as _, Ledger},
+    token::StellarAssetClient,
+    vec, Address, Env, Vec,
+};
+
+struct Setup<'a> {
+    client: StellarStreamContractClient<'a>,
+    sender: Address,
+    receiver: Address,
+    token: Address,
+    /// Weights 3, 2, 1, 1 and 1, threshold 5
+    members: [Address; 5],
+    council_id: u64,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().with_mut(|li| li.timestamp = 50);
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+    let receiver = Address::generate(env);
+
+    let (token, _token_client) = create_token_contract(env, &admin);
+    StellarAssetClient::new(env, &token).mint(&sender, &10_000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+
+    let members: [Address; 5] = core::array::from_fn(|_| Address::generate(env));
+    let council_id = client.create_council(&sender, &weighted(env, &members, &[3, 2, 1, 1, 1]), &5);
+
+    Setup {
+        client,
+        sender,
+        receiver,
+        token,
+        members,
+        council_id,
+    }
+}
+
+fn weighted(env: &Env, members: &[Address], weights: &[u32]) -> Vec<CouncilMember> {
+    let mut list = Vec::new(env);
+    for (member, weight) in members.iter().zip(weights) {
+        list.push_back(CouncilMember {
+            member: member.clone(),
+            weight: *weight,
+        });
+    }
+    list
+}
+
+fn council_proposal(env: &Env, s: &Setup) -> u64 {
+    let config = ProposalConfig {
+        required_approvals: 0,
+        required_rejections: 0,
+        voters: Vec::new(env),
+        deadline: 1000,
+        escrow: false,
+        council_id: Some(s.council_id),
+        timelock: None,
+    };
+    s.client.create_proposal_with_config(
+        &s.sender,
+        &s.receiver,
+        &s.token,
+        &1000,
+        &100,
+        &200,
+        &StreamOptions::new(env),
+        &config,
+    )
+}
+
+#[test]
+fn test_council_proposal_executes_at_weight_threshold() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let proposal_id = council_proposal(&env, &s);
+    let proposal = s.client.get_proposal(&proposal_id);
+    assert_eq!(proposal.required_approvals, 5);
+    assert_eq!(proposal.required_rejections, 4);
+
+    s.client.approve_proposal(&proposal_id, &s.members[0]);
+    s.client.approve_proposal(&proposal_id, &s.members[2]);
+    assert_eq!(s.client.get_proposal(&proposal_id).approval_weight, 4);
+
+    s.client.approve_proposal(&proposal_id, &s.members[3]);
+    let result = s.client.try_get_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+}
+
+#[test]
+fn test_blocking_weight_discards_council_proposal() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let proposal_id = council_proposal(&env, &s);
+    s.client.reject_proposal(&proposal_id, &s.members[0]);
+    assert_eq!(s.client.get_proposal(&proposal_id).rejection_weight, 3);
+
+    // 3 + 1 rejected leaves 4, short of the threshold of 5
+    s.client.reject_proposal(&proposal_id, &s.members[4]);
+    let result = s.client.try_get_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+}
+
+#[test]
+fn test_only_members_vote_on_council_proposals() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let proposal_id = council_proposal(&env, &s);
+    let outsider = Address::generate(&env);
+    let result = s.client.try_approve_proposal(&proposal_id, &outsider);
+    assert_eq!(result, Err(Ok(Error::NotCouncilMember)));
+    let result = s.client.try_reject_proposal(&proposal_id, &outsider);
+    assert_eq!(result, Err(Ok(Error::NotCouncilMember)));
+}
+
+#[test]
+fn test_proposal_with_unknown_council_fails() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let config = ProposalConfig {
+        required_approvals: 1,
+        required_rejections: 1,
+        voters: Vec::new(&env),
+        deadline: 1000,
+        escrow: false,
+        council_id: Some(7),
+        timelock: None,
+    };
+    let result = s.client.try_create_proposal_with_config(
+        &s.sender,
+        &s.receiver,
+        &s.token,
+        &1000,
+        &100,
+        &200,
+        &StreamOptions::new(&env),
+        &config,
+    );
+    assert_eq!(result, Err(Ok(Error::CouncilNotFound)));
+}
+
+#[test]
+fn test_invalid_councils_rejected() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let (a, b) = (Address::generate(&env), Address::generate(&env));
+
+    let cases = [
+        (Vec::new(&env), 1),
+        (weighted(&env, &[a.clone(), b.clone()], &[1, 0]), 1),
+        (weighted(&env, &[a.clone(), a.clone()], &[1, 1]), 1),
+        (weighted(&env, &[a.clone(), b.clone()], &[1, 1]), 0),
+        (weighted(&env, &[a.clone(), b.clone()], &[1, 1]), 3),
+    ];
+    for (members, threshold) in cases {
+        let result = s.client.try_create_council(&s.sender, &members, &threshold);
+        assert_eq!(result, Err(Ok(Error::InvalidCouncil)));
+    }
+}
+
+#[test]
+fn test_membership_change_needs_council_approval() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let newcomer = Address::generate(&env);
+
+    let new_members = vec![
+        &env,
+        CouncilMember {
+            member: s.members[0].clone(),
+            weight: 1,
+        },
+        CouncilMember {
+            member: newcomer.clone(),
+            weight: 1,
+        },
+    ];
+
+    let result = s
+        .client
+        .try_propose_council_update(&s.council_id, &newcomer, &new_members, &2);
+    assert_eq!(result, Err(Ok(Error::NotCouncilMember)));
+
+    // The proposer's weight of 3 counts, but falls short of 5
+    s.client
+        .propose_council_update(&s.council_id, &s.members[0], &new_members, &2);
+    assert_eq!(s.client.get_council(&s.council_id).threshold, 5);
+    let result = s
+        .client
+        .try_approve_council_update(&s.council_id, &s.members[0]);
+    assert_eq!(result, Err(Ok(Error::AlreadyApproved)));
+
+    s.client
+        .approve_council_update(&s.council_id, &s.members[1]);
+    let council = s.client.get_council(&s.council_id);
+    assert_eq!(council.members, new_members);
+    assert_eq!(council.threshold, 2);
+    assert_eq!(s.client.get_council_update(&s.council_id), None);
+
+    let result = s
+        .client
+        .try_approve_council_update(&s.council_id, &s.members[1]);
+    assert_eq!(result, Err(Ok(Error::NoPendingCouncilUpdate)));
+
+    // Removed members can no longer vote on the council's proposals
+    let proposal_id = council_proposal(&env, &s);
+    let result = s.client.try_approve_proposal(&proposal_id, &s.members[1]);
+    assert_eq!(result, Err(Ok(Error::NotCouncilMember)));
+    s.client.approve_proposal(&proposal_id, &newcomer);
+    s.client.approve_proposal(&proposal_id, &s.members[0]);
+    let result = s.client.try_get_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+}
+
+#[test]
+fn test_membership_change_applies_to_pending_proposals() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let proposal_id = council_proposal(&env, &s);
+    s.client.approve_proposal(&proposal_id, &s.members[0]);
+    s.client.approve_proposal(&proposal_id, &s.members[3]);
+    assert_eq!(s.client.get_proposal(&proposal_id).approval_weight, 4);
+
+    // Shrink the council to three equal members who must all agree
+    let members = weighted(&env, &s.members[..3], &[1, 1, 1]);
+    s.client
+        .propose_council_update(&s.council_id, &s.members[0], &members, &3);
+    s.client
+        .approve_council_update(&s.council_id, &s.members[1]);
+
+    // Under the old threshold of 5 this vote would have executed the
+    // proposal; now the removed member's vote is dropped and 2 of 3 is short
+    s.client.approve_proposal(&proposal_id, &s.members[1]);
+    let proposal = s.client.get_proposal(&proposal_id);
+    assert_eq!(proposal.required_approvals, 3);
+    assert_eq!(proposal.required_rejections, 1);
+    assert_eq!(proposal.approval_weight, 2);
+
+    // One of three now blocks it
+    s.client.reject_proposal(&proposal_id, &s.members[2]);
+    let result = s.client.try_get_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+}
+
+#[test]
+fn test_pending_council_update_blocks_new_proposals() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let members = weighted(&env, &s.members[..2], &[1, 1]);
+
+    s.client
+        .propose_council_update(&s.council_id, &s.members[0], &members, &2);
+    let result = s
+        .client
+        .try_propose_council_update(&s.council_id, &s.members[1], &members, &1);
+    assert_eq!(result, Err(Ok(Error::CouncilUpdatePending)));
+
+    // Only the proposer can withdraw the pending change
+    let result = s
+        .client
+        .try_cancel_council_update(&s.council_id, &s.members[1]);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    s.client.cancel_council_update(&s.council_id, &s.members[0]);
+    assert_eq!(s.client.get_council_update(&s.council_id), None);
+
+    s.client
+        .propose_council_update(&s.council_id, &s.members[1], &members, &1);
+    let update = s.client.get_council_update(&s.council_id).unwrap();
+    assert_eq!(update.proposer, s.members[1]);
+    assert_eq!(update.threshold, 1);
+}
+
+#[test]
+fn test_council_update_expires() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let members = weighted(&env, &s.members[..2], &[1, 1]);
+
+    s.client
+        .propose_council_update(&s.council_id, &s.members[0], &members, &2);
+    let expires_at = 50 + COUNCIL_UPDATE_LIFETIME;
+    assert_eq!(
+        s.client
+            .get_council_update(&s.council_id)
+            .unwrap()
+            .expires_at,
+        expires_at
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = expires_at + 1);
+    assert_eq!(s.client.get_council_update(&s.council_id), None);
+    let result = s
+        .client
+        .try_approve_council_update(&s.council_id, &s.members[1]);
+    assert_eq!(result, Err(Ok(Error::NoPendingCouncilUpdate)));
+
+    s.client
+        .propose_council_update(&s.council_id, &s.members[1], &members, &2);
+    assert_eq!(s.client.get_council(&s.council_id).threshold, 5);
+}
+
+#[test]
+fn test_loading_council_extends_ttl() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let key = DataKey::Council(s.council_id);
+    // Keep the contract itself alive for the jump
+    env.as_contract(&s.client.address, || {
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_EXTEND_TO, TTL_EXTEND_TO)
+    });
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number += TTL_EXTEND_TO - TTL_THRESHOLD + 1);
+    env.as_contract(&s.client.address, || {
+        assert!(env.storage().persistent().get_ttl(&key) < TTL_THRESHOLD);
+        storage::load_council(&env, s.council_id);
+        assert_eq!(env.storage().persistent().get_ttl(&key), TTL_EXTEND_TO);
+    });
+}
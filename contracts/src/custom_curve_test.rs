@@ -0,0 +1,141 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::setup_test;
+use crate::types::{CurvePoint, CurveType, StreamOptions, TopUpMode};
+use crate::StellarStreamContractClient;
+use soroban_sdk::{testutils::Ledger, vec, Address, Env, Vec};
+
+fn point(time_offset: u64, unlocked_bps: u32) -> CurvePoint {
+    CurvePoint {
+        time_offset,
+        unlocked_bps,
+    }
+}
+
+fn try_create_with_curve(
+    env: &Env,
+    client: &StellarStreamContractClient,
+    sender: &Address,
+    receiver: &Address,
+    token: &Address,
+    points: Vec<CurvePoint>,
+) -> Result<u64, Error> {
+    let options = StreamOptions {
+        curve_type: CurveType::Custom(points),
+        ..StreamOptions::new(env)
+    };
+    client
+        .try_create_stream_with_options(sender, receiver, token, &1000, &0, &100, &options)
+        .map(|id| id.unwrap())
+        .map_err(|e| e.unwrap())
+}
+
+#[test]
+fn test_custom_curve_unlocks_along_breakpoints() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+
+    // 20% at 10s, nothing more until 60s, then the rest by the end
+    let points = vec![&env, point(10, 2_000), point(60, 2_000), point(100, 10_000)];
+    let stream_id =
+        try_create_with_curve(&env, &client, &sender, &receiver, &token, points).unwrap();
+
+    env.ledger().with_mut(|li| li.timestamp = 5);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 100);
+
+    env.ledger().with_mut(|li| li.timestamp = 40);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 100);
+    assert_eq!(
+        client.try_withdraw(&stream_id, &receiver),
+        Err(Ok(Error::InsufficientBalance))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 80);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 400);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 400);
+    assert_eq!(token_client.balance(&receiver), 1000);
+}
+
+#[test]
+fn test_custom_curve_respects_pause() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let points = vec![&env, point(50, 5_000), point(100, 10_000)];
+    let stream_id =
+        try_create_with_curve(&env, &client, &sender, &receiver, &token, points).unwrap();
+
+    env.ledger().with_mut(|li| li.timestamp = 25);
+    client.pause_stream(&stream_id, &sender);
+    env.ledger().with_mut(|li| li.timestamp = 75);
+    client.unpause_stream(&stream_id, &sender);
+
+    // 50s paused, so only 50s of the schedule have elapsed
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 500);
+}
+
+#[test]
+fn test_invalid_custom_curves_rejected() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let invalid = [
+        // No breakpoints
+        Vec::new(&env),
+        // Does not reach 100%
+        vec![&env, point(100, 9_000)],
+        // Ends before the stream does
+        vec![&env, point(90, 10_000)],
+        // Unlocked share decreases
+        vec![&env, point(50, 6_000), point(70, 5_000), point(100, 10_000)],
+        // Offsets not strictly increasing
+        vec![&env, point(50, 5_000), point(50, 6_000), point(100, 10_000)],
+        // Beyond 100%
+        vec![&env, point(50, 10_001), point(100, 10_000)],
+    ];
+
+    for points in invalid {
+        assert_eq!(
+            try_create_with_curve(&env, &client, &sender, &receiver, &token, points),
+            Err(Error::InvalidCurve)
+        );
+    }
+
+    let mut too_many = Vec::new(&env);
+    for i in 1..33u64 {
+        too_many.push_back(point(i, (i * 300) as u32));
+    }
+    too_many.push_back(point(100, 10_000));
+    assert_eq!(
+        try_create_with_curve(&env, &client, &sender, &receiver, &token, too_many),
+        Err(Error::InvalidCurve)
+    );
+}
+
+#[test]
+fn test_increase_rate_top_up_follows_rest_of_custom_curve() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let points = vec![&env, point(50, 2_000), point(100, 10_000)];
+    let stream_id =
+        try_create_with_curve(&env, &client, &sender, &receiver, &token, points).unwrap();
+
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    client.top_up_stream(&stream_id, &sender, &1000, &TopUpMode::IncreaseRate);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.total_amount, 2000);
+    assert_eq!(stream.end_time, 100);
+
+    // 40 had unlocked; the other 1960 follow the rest of the curve, which
+    // reaches 2_000 bps at 50 having started from 400 bps at 10
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 366);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 1634);
+}
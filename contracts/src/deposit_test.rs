@@ -0,0 +1,237 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::create_token_contract;
+use crate::types::TopUpMode;
+use crate::{StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+fn setup_test(
+    env: &Env,
+) -> (
+    StellarStreamContractClient<'_>,
+    Address,
+    Address,
+    TokenClient<'_>,
+) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+
+    let (token_address, token_client) = create_token_contract(env, &admin);
+    StellarAssetClient::new(env, &token_address).mint(&sender, &10_000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+
+    (client, sender, token_address, token_client)
+}
+
+#[test]
+fn test_one_deposit_funds_many_streams() {
+    let env = Env::default();
+    let (client, sender, token, token_client) = setup_test(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    assert_eq!(client.deposit(&sender, &token, &1000), 1000);
+    let alice_stream = client.create_deposit_stream(&sender, &alice, &token, &3);
+    let bob_stream = client.create_deposit_stream(&sender, &bob, &token, &2);
+
+    // Nothing is escrowed per stream
+    assert_eq!(token_client.balance(&client.address), 1000);
+    assert_eq!(client.get_stream(&alice_stream).total_amount, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.withdraw(&alice_stream, &alice), 300);
+    assert_eq!(client.withdraw(&bob_stream, &bob), 200);
+
+    let solvency = client.get_deposit_solvency(&sender, &token);
+    assert_eq!(solvency.balance, 500);
+    assert_eq!(solvency.outgoing_rate, 5);
+    assert_eq!(solvency.depletion_time, Some(200));
+    assert!(!solvency.is_depleted);
+}
+
+#[test]
+fn test_deposit_must_fund_first_second() {
+    let env = Env::default();
+    let (client, sender, token, _) = setup_test(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    assert_eq!(
+        client.try_create_deposit_stream(&sender, &alice, &token, &3),
+        Err(Ok(Error::InsufficientBalance))
+    );
+
+    client.deposit(&sender, &token, &5);
+    client.create_deposit_stream(&sender, &alice, &token, &3);
+
+    // 5 cannot cover a second of 3 + 3
+    assert_eq!(
+        client.try_create_deposit_stream(&sender, &bob, &token, &3),
+        Err(Ok(Error::InsufficientBalance))
+    );
+    client.create_deposit_stream(&sender, &bob, &token, &2);
+}
+
+#[test]
+fn test_outgoing_rate_overflow_rejected() {
+    let env = Env::default();
+    let (client, sender, token, _) = setup_test(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.deposit(&sender, &token, &10_000);
+    client.create_deposit_stream(&sender, &alice, &token, &3);
+
+    assert_eq!(
+        client.try_create_deposit_stream(&sender, &bob, &token, &i128::MAX),
+        Err(Ok(Error::InvalidAmount))
+    );
+    assert_eq!(
+        client.get_deposit_solvency(&sender, &token).outgoing_rate,
+        3
+    );
+}
+
+#[test]
+fn test_withdraw_excess_is_limited_to_undrawn_balance() {
+    let env = Env::default();
+    let (client, sender, token, token_client) = setup_test(&env);
+    let receiver = Address::generate(&env);
+
+    client.deposit(&sender, &token, &1000);
+    client.create_deposit_stream(&sender, &receiver, &token, &5);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(
+        client.try_withdraw_excess(&sender, &token, &501),
+        Err(Ok(Error::InsufficientBalance))
+    );
+    assert_eq!(client.withdraw_excess(&sender, &token, &100), 400);
+    assert_eq!(token_client.balance(&sender), 9_100);
+
+    // Less runway for the remaining balance
+    let solvency = client.get_deposit_solvency(&sender, &token);
+    assert_eq!(solvency.depletion_time, Some(180));
+}
+
+#[test]
+fn test_streams_stop_drawing_when_deposit_runs_out() {
+    let env = Env::default();
+    let (client, sender, token, token_client) = setup_test(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    // 1001 at 5 per second: 200 seconds, 1 left over
+    client.deposit(&sender, &token, &1001);
+    let alice_stream = client.create_deposit_stream(&sender, &alice, &token, &3);
+    client.create_deposit_stream(&sender, &bob, &token, &2);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(
+        client.try_liquidate_deposit(&sender, &token),
+        Err(Ok(Error::DepositNotDepleted))
+    );
+    client.withdraw(&alice_stream, &alice);
+
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    assert_eq!(client.withdraw(&alice_stream, &alice), 300);
+    let solvency = client.get_deposit_solvency(&sender, &token);
+    assert_eq!(solvency.balance, 1);
+    assert!(solvency.is_depleted);
+
+    // Anyone can liquidate; bob is paid what the deposit funded
+    assert_eq!(client.liquidate_deposit(&sender, &token), 2);
+    assert_eq!(token_client.balance(&alice), 600);
+    assert_eq!(token_client.balance(&bob), 400);
+    assert!(client.get_stream(&alice_stream).cancelled);
+
+    let solvency = client.get_deposit_solvency(&sender, &token);
+    assert_eq!(solvency.outgoing_rate, 0);
+    assert_eq!(solvency.depletion_time, None);
+    assert_eq!(client.withdraw_excess(&sender, &token, &1), 0);
+}
+
+#[test]
+fn test_refilling_depleted_deposit_pays_the_gap() {
+    let env = Env::default();
+    let (client, sender, token, _) = setup_test(&env);
+    let receiver = Address::generate(&env);
+
+    client.deposit(&sender, &token, &100);
+    let stream_id = client.create_deposit_stream(&sender, &receiver, &token, &10);
+
+    // Ran out at 10; a new stream cannot start on an empty deposit
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_create_deposit_stream(&sender, &other, &token, &10),
+        Err(Ok(Error::InsufficientBalance))
+    );
+
+    client.deposit(&sender, &token, &200);
+    assert_eq!(
+        client.get_deposit_solvency(&sender, &token).depletion_time,
+        Some(30)
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 25);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 250);
+}
+
+#[test]
+fn test_cancel_deposit_stream_keeps_funds_in_deposit() {
+    let env = Env::default();
+    let (client, sender, token, token_client) = setup_test(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.deposit(&sender, &token, &1000);
+    let alice_stream = client.create_deposit_stream(&sender, &alice, &token, &3);
+    client.create_deposit_stream(&sender, &bob, &token, &2);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    client.cancel(&alice_stream, &sender);
+    assert_eq!(token_client.balance(&alice), 150);
+    assert_eq!(token_client.balance(&sender), 9_000);
+
+    let solvency = client.get_deposit_solvency(&sender, &token);
+    assert_eq!(solvency.balance, 750);
+    assert_eq!(solvency.outgoing_rate, 2);
+    assert_eq!(solvency.depletion_time, Some(425));
+}
+
+#[test]
+fn test_deposit_streams_reject_escrow_operations() {
+    let env = Env::default();
+    let (client, sender, token, _) = setup_test(&env);
+    let receiver = Address::generate(&env);
+
+    client.deposit(&sender, &token, &1000);
+    let stream_id = client.create_deposit_stream(&sender, &receiver, &token, &5);
+
+    assert_eq!(
+        client.try_top_up_stream(&stream_id, &sender, &100, &TopUpMode::ExtendDuration),
+        Err(Ok(Error::DepositFundedStream))
+    );
+    assert_eq!(
+        client.try_pause_stream(&stream_id, &sender),
+        Err(Ok(Error::DepositFundedStream))
+    );
+    assert_eq!(
+        client.try_update_stream_rate(&stream_id, &sender, &10),
+        Err(Ok(Error::DepositFundedStream))
+    );
+    assert_eq!(
+        client.try_create_deposit_stream(&sender, &receiver, &token, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
@@ -1,4 +1,5 @@
 #![cfg(test)]
+use crate::test_utils::create_token_contract;
 use crate::{StellarStreamContract, StellarStreamContractClient};
 use soroban_sdk::{
     testutils::{Address as _, Ledger, LedgerInfo},
@@ -6,13 +7,6 @@ use soroban_sdk::{
     Address, Env, Vec,
 };
 
-fn create_token_contract<'a>(env: &Env, admin: &Address) -> (Address, TokenClient<'a>) {
-    let contract_id = env
-        .register_stellar_asset_contract_v2(admin.clone())
-        .address();
-    (contract_id.clone(), TokenClient::new(env, &contract_id))
-}
-
 #[test]
 fn test_freeze_stream() {
     let env = Env::default();
@@ -53,7 +47,7 @@ fn test_freeze_stream() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #22)")]
+#[should_panic(expected = "Error(Contract, #27)")]
 fn test_withdraw_from_frozen_stream_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -182,3 +176,50 @@ fn test_non_arbiter_cannot_freeze() {
     client.set_arbiter(&stream_id, &sender, &arbiter);
     client.freeze_stream(&stream_id, &non_arbiter);
 }
+
+#[test]
+fn test_frozen_stream_cannot_be_cancelled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let receiver = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let other_arbiter = Address::generate(&env);
+
+    let (token_address, _token_client) = create_token_contract(&env, &admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_address);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let milestones = Vec::new(&env);
+    let stream_id = client.create_stream_with_milestones(
+        &sender,
+        &receiver,
+        &token_address,
+        &1000,
+        &100,
+        &200,
+        &milestones,
+        &crate::types::CurveType::Linear,
+        &false,
+        &None,
+    );
+
+    client.set_arbiter(&stream_id, &sender, &arbiter);
+    client.freeze_stream(&stream_id, &arbiter);
+
+    // Neither party can settle or swap the arbiter while the dispute is open
+    assert_eq!(
+        client.try_cancel(&stream_id, &sender),
+        Err(Ok(crate::errors::Error::StreamFrozen))
+    );
+    assert_eq!(
+        client.try_set_arbiter(&stream_id, &sender, &other_arbiter),
+        Err(Ok(crate::errors::Error::Unauthorized))
+    );
+}
@@ -27,4 +27,61 @@ pub enum Error {
     StreamIsSoulbound = 21,
     /// Address is restricted by OFAC compliance
     AddressRestricted = 22,
+    /// Milestone is outside the stream window or percentages exceed 100
+    InvalidMilestone = 23,
+    /// Interest strategy bits outside the supported sender/receiver/protocol set
+    InvalidInterestStrategy = 24,
+    /// Fee above MAX_FEE_BPS
+    InvalidFee = 25,
+    /// Flow is stopped by the contract-wide circuit breaker
+    ContractPaused = 26,
+    /// Stream is frozen pending dispute resolution
+    StreamFrozen = 27,
+    /// The stream's cancellation policy does not allow this caller to cancel now
+    CancellationNotAllowed = 28,
+    /// Custom curve breakpoints are not monotonic or do not end at 100%
+    InvalidCurve = 29,
+    /// Initial unlock above 10_000 bps
+    InvalidInitialUnlock = 30,
+    /// Operation only applies to open-ended rate streams
+    NotRateStream = 31,
+    /// Sender's shared deposit still funds its streams
+    DepositNotDepleted = 32,
+    /// Shared deposit already funds MAX_DEPOSIT_STREAMS streams
+    TooManyDepositStreams = 33,
+    /// Operation does not apply to streams funded from a shared deposit
+    DepositFundedStream = 34,
+    /// No amendment is pending for the stream
+    NoPendingAmendment = 35,
+    /// Accepted terms differ from the pending amendment
+    AmendmentMismatch = 36,
+    /// Amendment would take back unlocked funds or falls outside its window
+    InvalidAmendment = 37,
+    /// Address already rejected the proposal
+    AlreadyRejected = 38,
+    CouncilNotFound = 39,
+    /// Caller is not a member of the proposal's council
+    NotCouncilMember = 40,
+    /// Council is empty, too large, repeats a member, has a zero weight or
+    /// a threshold its members cannot reach
+    InvalidCouncil = 41,
+    /// No membership change is pending for the council
+    NoPendingCouncilUpdate = 42,
+    /// Voting is closed while the proposal waits out its timelock
+    ProposalQueued = 43,
+    /// Proposal has not reached quorum under a timelock
+    ProposalNotQueued = 44,
+    /// Proposal's timelock has not elapsed yet
+    TimelockActive = 45,
+    /// Open-ended stream owes more than its balance; top it up before the
+    /// sender cancels
+    OutstandingDebt = 46,
+    /// Proposals under a timelock must escrow their funds
+    EscrowRequired = 47,
+    /// A membership change is already pending for the council
+    CouncilUpdatePending = 48,
+    /// Batch holds more than `MAX_BATCH_SIZE` requests
+    BatchTooLarge = 49,
+    /// Token is not on the enabled allowlist
+    TokenNotAllowed = 50,
 }
@@ -0,0 +1,306 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::create_token_contract;
+use crate::types::{AmendmentTerms, CurveType, FeeMode, Role, TopUpMode};
+use crate::{StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+struct FeeTestContext<'a> {
+    client: StellarStreamContractClient<'a>,
+    admin: Address,
+    treasury: Address,
+    sender: Address,
+    receiver: Address,
+    token: Address,
+    token_client: TokenClient<'a>,
+}
+
+fn setup_test(env: &Env, fee_bps: u32) -> FeeTestContext<'_> {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let admin = Address::generate(env);
+    let treasury = Address::generate(env);
+    let sender = Address::generate(env);
+    let receiver = Address::generate(env);
+
+    let (token, token_client) = create_token_contract(env, &admin);
+    StellarAssetClient::new(env, &token).mint(&sender, &10_000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+    client.initialize(&admin);
+    client.initialize_fee(&admin, &fee_bps, &treasury);
+
+    FeeTestContext {
+        client,
+        admin,
+        treasury,
+        sender,
+        receiver,
+        token,
+        token_client,
+    }
+}
+
+#[test]
+fn test_fee_taken_on_creation() {
+    let env = Env::default();
+    let ctx = setup_test(&env, 100);
+
+    let stream_id = ctx.client.create_stream(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+
+    // 1% of the deposit goes to the treasury, the rest is streamed
+    assert_eq!(ctx.client.get_stream(&stream_id).total_amount, 990);
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 10);
+    assert_eq!(ctx.client.get_collected_fees(&ctx.token), 10);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(ctx.client.withdraw(&stream_id, &ctx.receiver), 990);
+    assert_eq!(ctx.token_client.balance(&ctx.receiver), 990);
+}
+
+#[test]
+fn test_fee_taken_on_withdrawal() {
+    let env = Env::default();
+    let ctx = setup_test(&env, 100);
+    ctx.client
+        .update_fee_mode(&ctx.admin, &FeeMode::OnWithdrawal);
+
+    let stream_id = ctx.client.create_stream(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(ctx.client.get_stream(&stream_id).total_amount, 1000);
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(ctx.client.withdraw(&stream_id, &ctx.receiver), 500);
+    assert_eq!(ctx.token_client.balance(&ctx.receiver), 495);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    ctx.client.withdraw(&stream_id, &ctx.receiver);
+    assert_eq!(ctx.token_client.balance(&ctx.receiver), 990);
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 10);
+    assert_eq!(ctx.client.get_collected_fees(&ctx.token), 10);
+}
+
+#[test]
+fn test_fee_taken_on_top_up() {
+    let env = Env::default();
+    let ctx = setup_test(&env, 100);
+
+    // A tiny stream cannot be topped up fee-free
+    let stream_id = ctx.client.create_stream(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token,
+        &1,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+    ctx.client
+        .top_up_stream(&stream_id, &ctx.sender, &5000, &TopUpMode::IncreaseRate);
+    assert_eq!(ctx.client.get_stream(&stream_id).total_amount, 1 + 4950);
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 50);
+
+    let rate_id =
+        ctx.client
+            .create_rate_stream(&ctx.sender, &ctx.receiver, &ctx.token, &10, &1000, &0);
+    ctx.client
+        .top_up_stream(&rate_id, &ctx.sender, &1000, &TopUpMode::ExtendDuration);
+    assert_eq!(ctx.client.get_stream(&rate_id).total_amount, 1980);
+    assert_eq!(ctx.client.get_collected_fees(&ctx.token), 50 + 10 + 10);
+
+    // Nothing extra is charged in withdrawal mode, where the fee is taken
+    // on the way out
+    ctx.client
+        .update_fee_mode(&ctx.admin, &FeeMode::OnWithdrawal);
+    ctx.client
+        .top_up_stream(&rate_id, &ctx.sender, &1000, &TopUpMode::ExtendDuration);
+    assert_eq!(ctx.client.get_stream(&rate_id).total_amount, 2980);
+}
+
+#[test]
+fn test_fee_taken_on_amendment_increase() {
+    let env = Env::default();
+    let ctx = setup_test(&env, 100);
+
+    let stream_id = ctx.client.create_stream(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+    let terms = |total_amount| AmendmentTerms {
+        total_amount,
+        end_time: 200,
+        cliff_time: 0,
+        curve_type: CurveType::Linear,
+        receiver: ctx.receiver.clone(),
+    };
+
+    // The sender escrows the increase and its fee with the proposal
+    ctx.client
+        .propose_amendment(&stream_id, &ctx.sender, &terms(2990));
+    assert_eq!(ctx.token_client.balance(&ctx.sender), 10_000 - 1000 - 2020);
+    ctx.client
+        .accept_amendment(&stream_id, &ctx.receiver, &terms(2990));
+    assert_eq!(ctx.client.get_stream(&stream_id).total_amount, 2990);
+    assert_eq!(ctx.client.get_collected_fees(&ctx.token), 10 + 20);
+
+    // A sender accepting the receiver's increase pays the fee on accepting
+    ctx.client
+        .propose_amendment(&stream_id, &ctx.receiver, &terms(3990));
+    ctx.client
+        .accept_amendment(&stream_id, &ctx.sender, &terms(3990));
+    assert_eq!(
+        ctx.token_client.balance(&ctx.sender),
+        10_000 - 1000 - 2020 - 1010
+    );
+    assert_eq!(ctx.client.get_collected_fees(&ctx.token), 10 + 20 + 10);
+
+    // Lowering the total refunds without a fee
+    ctx.client
+        .propose_amendment(&stream_id, &ctx.sender, &terms(3000));
+    ctx.client
+        .accept_amendment(&stream_id, &ctx.receiver, &terms(3000));
+    assert_eq!(
+        ctx.token_client.balance(&ctx.sender),
+        10_000 - 1000 - 2020 - 1010 + 990
+    );
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 40);
+}
+
+#[test]
+fn test_mode_change_does_not_double_charge() {
+    let env = Env::default();
+    let ctx = setup_test(&env, 100);
+
+    let stream_id = ctx.client.create_stream(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+    ctx.client
+        .update_fee_mode(&ctx.admin, &FeeMode::OnWithdrawal);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    ctx.client.withdraw(&stream_id, &ctx.receiver);
+    assert_eq!(ctx.token_client.balance(&ctx.receiver), 990);
+    assert_eq!(ctx.client.get_collected_fees(&ctx.token), 10);
+}
+
+#[test]
+fn test_fees_accumulate_per_token() {
+    let env = Env::default();
+    let ctx = setup_test(&env, 200);
+
+    let other_admin = Address::generate(&env);
+    let (other_token, _) = create_token_contract(&env, &other_admin);
+    StellarAssetClient::new(&env, &other_token).mint(&ctx.sender, &10_000);
+
+    for _ in 0..2 {
+        ctx.client.create_stream(
+            &ctx.sender,
+            &ctx.receiver,
+            &ctx.token,
+            &1000,
+            &0,
+            &100,
+            &CurveType::Linear,
+            &false,
+        );
+    }
+    ctx.client.create_stream(
+        &ctx.sender,
+        &ctx.receiver,
+        &other_token,
+        &5000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+
+    assert_eq!(ctx.client.get_collected_fees(&ctx.token), 40);
+    assert_eq!(ctx.client.get_collected_fees(&other_token), 100);
+}
+
+#[test]
+fn test_fee_capped() {
+    let env = Env::default();
+    let ctx = setup_test(&env, 100);
+
+    let result = ctx.client.try_update_fee(&ctx.admin, &1_001);
+    assert_eq!(result, Err(Ok(Error::InvalidFee)));
+
+    ctx.client.update_fee(&ctx.admin, &1_000);
+    assert_eq!(ctx.client.get_fee_bps(), 1_000);
+}
+
+#[test]
+fn test_only_treasury_manager_configures_fees() {
+    let env = Env::default();
+    let ctx = setup_test(&env, 100);
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        ctx.client.try_update_fee(&outsider, &200),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        ctx.client.try_update_treasury(&outsider, &outsider),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        ctx.client
+            .try_update_fee_mode(&outsider, &FeeMode::OnWithdrawal),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    let manager = Address::generate(&env);
+    ctx.client
+        .grant_role(&ctx.admin, &manager, &Role::TreasuryManager);
+    ctx.client.update_treasury(&manager, &manager);
+    assert_eq!(ctx.client.get_treasury(), Some(manager));
+}
+
+#[test]
+fn test_fee_cannot_be_initialized_twice() {
+    let env = Env::default();
+    let ctx = setup_test(&env, 100);
+
+    let result = ctx.client.try_initialize_fee(&ctx.admin, &50, &ctx.admin);
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+}
@@ -0,0 +1,207 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::create_token_contract;
+use crate::types::{CurveType, PauseState, Role, TopUpMode};
+use crate::{StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    Address, Env,
+};
+
+fn setup_test(
+    env: &Env,
+) -> (
+    StellarStreamContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+    let receiver = Address::generate(env);
+
+    let (token, _) = create_token_contract(env, &admin);
+    StellarAssetClient::new(env, &token).mint(&sender, &10_000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+    client.initialize(&admin);
+
+    (client, admin, sender, receiver, token)
+}
+
+#[test]
+fn test_set_pause_stops_every_flow() {
+    let env = Env::default();
+    let (client, admin, sender, receiver, token) = setup_test(&env);
+
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+
+    client.set_pause(&admin, &true);
+    assert_eq!(
+        client.get_pause_state(),
+        PauseState {
+            creation: true,
+            withdrawal: true,
+            admin: true,
+        }
+    );
+
+    let result = client.try_create_stream(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(
+        client.try_withdraw(&stream_id, &receiver),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_cancel(&stream_id, &sender),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_top_up_stream(&stream_id, &sender, &100, &TopUpMode::ExtendDuration),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_transfer_receiver(&stream_id, &sender, &sender),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_create_proposal(&sender, &receiver, &token, &1000, &100, &200, &1, &1000),
+        Err(Ok(Error::ContractPaused))
+    );
+
+    client.set_pause(&admin, &false);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 500);
+}
+
+#[test]
+fn test_creation_pause_still_allows_withdrawals() {
+    let env = Env::default();
+    let (client, admin, sender, receiver, token) = setup_test(&env);
+
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+    let rate_id = client.create_rate_stream(&sender, &receiver, &token, &10, &1000, &0);
+
+    client.set_pause_state(
+        &admin,
+        &PauseState {
+            creation: true,
+            withdrawal: false,
+            admin: false,
+        },
+    );
+
+    let result = client.try_create_stream(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+    assert_eq!(
+        client.try_top_up_stream(&stream_id, &sender, &100, &TopUpMode::ExtendDuration),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_update_stream_rate(&rate_id, &sender, &20),
+        Err(Ok(Error::ContractPaused))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 40);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 400);
+    client.cancel(&stream_id, &sender);
+    assert!(client.get_stream(&stream_id).cancelled);
+}
+
+#[test]
+fn test_admin_pause_blocks_receiver_transfer_only() {
+    let env = Env::default();
+    let (client, admin, sender, receiver, token) = setup_test(&env);
+
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+    let rate_id = client.create_rate_stream(&sender, &receiver, &token, &10, &1000, &0);
+
+    client.set_pause_state(
+        &admin,
+        &PauseState {
+            creation: false,
+            withdrawal: false,
+            admin: true,
+        },
+    );
+
+    let new_receiver = Address::generate(&env);
+    assert_eq!(
+        client.try_transfer_receiver(&stream_id, &sender, &new_receiver),
+        Err(Ok(Error::ContractPaused))
+    );
+    // Rate changes follow the creation switch like top-ups
+    client.update_stream_rate(&rate_id, &sender, &20);
+
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 100);
+}
+
+#[test]
+fn test_only_pauser_can_pause() {
+    let env = Env::default();
+    let (client, admin, _, _, _) = setup_test(&env);
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_set_pause(&outsider, &true),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    let pauser = Address::generate(&env);
+    client.grant_role(&admin, &pauser, &Role::Pauser);
+    client.set_pause(&pauser, &true);
+    assert!(client.get_pause_state().creation);
+}
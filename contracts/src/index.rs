@@ -0,0 +1,115 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::storage::{TTL_EXTEND_TO, TTL_THRESHOLD};
+use crate::types::{DataKey, StreamIndex, StreamPage};
+
+/// Upper bound on the number of slots read by one page query
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+// Each address owns a list of stream ids stored one entry per slot:
+// `IndexLen(kind, owner)` holds the number of slots used, `IndexCount(kind,
+// owner)` the number of streams still listed and `IndexEntry(kind, owner,
+// slot)` the id in each slot. `IndexPos(kind, stream_id)` records the slot a
+// stream occupies so removal is constant-time. Removal empties the slot
+// without moving any other entry, so a slot cursor stays valid across
+// removals and paging never skips or repeats an id.
+
+fn bump(env: &Env, key: &DataKey) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+fn slots(env: &Env, kind: StreamIndex, owner: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IndexLen(kind, owner.clone()))
+        .unwrap_or(0)
+}
+
+fn set_count(env: &Env, kind: StreamIndex, owner: &Address, count: u32) {
+    let count_key = DataKey::IndexCount(kind, owner.clone());
+    env.storage().persistent().set(&count_key, &count);
+    bump(env, &count_key);
+}
+
+/// Number of streams listed for `owner`
+pub fn len(env: &Env, kind: StreamIndex, owner: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IndexCount(kind, owner.clone()))
+        .unwrap_or(0)
+}
+
+pub fn add(env: &Env, kind: StreamIndex, owner: &Address, stream_id: u64) {
+    let slot = slots(env, kind, owner);
+    let len_key = DataKey::IndexLen(kind, owner.clone());
+    let entry_key = DataKey::IndexEntry(kind, owner.clone(), slot);
+    let pos_key = DataKey::IndexPos(kind, stream_id);
+
+    env.storage().persistent().set(&entry_key, &stream_id);
+    env.storage().persistent().set(&pos_key, &slot);
+    env.storage().persistent().set(&len_key, &(slot + 1));
+    bump(env, &entry_key);
+    bump(env, &pos_key);
+    bump(env, &len_key);
+    set_count(env, kind, owner, len(env, kind, owner) + 1);
+}
+
+pub fn remove(env: &Env, kind: StreamIndex, owner: &Address, stream_id: u64) {
+    let pos_key = DataKey::IndexPos(kind, stream_id);
+    let Some(slot) = env.storage().persistent().get::<_, u32>(&pos_key) else {
+        return;
+    };
+    // Only touch the list if it is `owner`'s that holds the stream
+    let entry_key = DataKey::IndexEntry(kind, owner.clone(), slot);
+    if env.storage().persistent().get::<_, u64>(&entry_key) != Some(stream_id) {
+        return;
+    }
+    let Some(count) = len(env, kind, owner).checked_sub(1) else {
+        return;
+    };
+
+    env.storage().persistent().remove(&entry_key);
+    env.storage().persistent().remove(&pos_key);
+
+    // Slots are never reused, even once the list is empty, so a cursor
+    // handed out earlier still points past everything it has seen
+    if count == 0 {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::IndexCount(kind, owner.clone()));
+    } else {
+        set_count(env, kind, owner, count);
+    }
+}
+
+/// Move a stream from one owner's list to another's
+pub fn transfer(env: &Env, kind: StreamIndex, from: &Address, to: &Address, stream_id: u64) {
+    if from == to {
+        return;
+    }
+    remove(env, kind, from, stream_id);
+    add(env, kind, to, stream_id);
+}
+
+/// Stream ids in slots `cursor..cursor + limit`, with `limit` capped at
+/// `MAX_PAGE_SIZE`. Emptied slots are skipped, so a page can hold fewer
+/// ids than `limit` even when more follow.
+pub fn page(env: &Env, kind: StreamIndex, owner: &Address, cursor: u32, limit: u32) -> StreamPage {
+    let slots = slots(env, kind, owner);
+    let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(slots);
+
+    let mut stream_ids = Vec::new(env);
+    for slot in cursor..end {
+        let key = DataKey::IndexEntry(kind, owner.clone(), slot);
+        if let Some(stream_id) = env.storage().persistent().get::<_, u64>(&key) {
+            bump(env, &key);
+            stream_ids.push_back(stream_id);
+        }
+    }
+    StreamPage {
+        stream_ids,
+        next_cursor: (end < slots).then_some(end),
+    }
+}
@@ -0,0 +1,213 @@
+#![cfg(test)]
+use crate::index;
+use crate::test_utils::create_token_contract;
+use crate::types::{CurveType, StreamIndex};
+use crate::{StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    vec, Address, Env,
+};
+
+fn setup_test(env: &Env) -> (StellarStreamContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+
+    let (token, _) = create_token_contract(env, &admin);
+    StellarAssetClient::new(env, &token).mint(&sender, &1_000_000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+
+    (client, sender, token)
+}
+
+fn create(
+    client: &StellarStreamContractClient,
+    sender: &Address,
+    receiver: &Address,
+    token: &Address,
+) -> u64 {
+    client.create_stream(
+        sender,
+        receiver,
+        token,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    )
+}
+
+#[test]
+fn test_streams_listed_by_sender_and_receiver() {
+    let env = Env::default();
+    let (client, sender, token) = setup_test(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let s0 = create(&client, &sender, &alice, &token);
+    let s1 = create(&client, &sender, &bob, &token);
+    let s2 = create(&client, &sender, &alice, &token);
+
+    assert_eq!(client.get_sender_stream_count(&sender), 3);
+    assert_eq!(
+        client.get_streams_by_sender(&sender, &0, &10).stream_ids,
+        vec![&env, s0, s1, s2]
+    );
+    assert_eq!(
+        client.get_streams_by_receiver(&alice, &0, &10).stream_ids,
+        vec![&env, s0, s2]
+    );
+    assert_eq!(
+        client.get_streams_by_receiver(&bob, &0, &10).stream_ids,
+        vec![&env, s1]
+    );
+    assert_eq!(client.get_receiver_stream_count(&sender), 0);
+}
+
+#[test]
+fn test_pagination_with_cursor() {
+    let env = Env::default();
+    let (client, sender, token) = setup_test(&env);
+    let receiver = Address::generate(&env);
+
+    for _ in 0..5 {
+        create(&client, &sender, &receiver, &token);
+    }
+
+    let page = client.get_streams_by_sender(&sender, &0, &2);
+    assert_eq!(page.stream_ids, vec![&env, 0, 1]);
+    assert_eq!(page.next_cursor, Some(2));
+
+    let page = client.get_streams_by_sender(&sender, &2, &2);
+    assert_eq!(page.stream_ids, vec![&env, 2, 3]);
+    assert_eq!(page.next_cursor, Some(4));
+
+    let page = client.get_streams_by_sender(&sender, &4, &2);
+    assert_eq!(page.stream_ids, vec![&env, 4]);
+    assert_eq!(page.next_cursor, None);
+
+    let page = client.get_streams_by_sender(&sender, &u32::MAX, &2);
+    assert_eq!(page.stream_ids.len(), 0);
+    assert_eq!(page.next_cursor, None);
+}
+
+#[test]
+fn test_removal_during_pagination_keeps_cursor_valid() {
+    let env = Env::default();
+    let (client, sender, token) = setup_test(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let ids = [(); 6].map(|_| create(&client, &sender, &alice, &token));
+
+    let page = client.get_streams_by_receiver(&alice, &0, &3);
+    assert_eq!(page.stream_ids, vec![&env, ids[0], ids[1], ids[2]]);
+
+    // Streams leave the list on both sides of the cursor between calls
+    client.transfer_receiver(&ids[0], &sender, &bob);
+    client.transfer_receiver(&ids[4], &sender, &bob);
+
+    // Nothing is skipped or repeated: the rest of the list, minus the one
+    // that left
+    let page = client.get_streams_by_receiver(&alice, &page.next_cursor.unwrap(), &3);
+    assert_eq!(page.stream_ids, vec![&env, ids[3], ids[5]]);
+    assert_eq!(page.next_cursor, None);
+
+    // A fresh listing keeps creation order
+    assert_eq!(
+        client.get_streams_by_receiver(&alice, &0, &10).stream_ids,
+        vec![&env, ids[1], ids[2], ids[3], ids[5]]
+    );
+    assert_eq!(client.get_receiver_stream_count(&alice), 4);
+}
+
+#[test]
+fn test_page_size_is_capped() {
+    let env = Env::default();
+    let (client, sender, token) = setup_test(&env);
+    let receiver = Address::generate(&env);
+
+    for _ in 0..105 {
+        create(&client, &sender, &receiver, &token);
+    }
+
+    let page = client.get_streams_by_receiver(&receiver, &0, &1000);
+    assert_eq!(page.stream_ids.len(), 100);
+    assert_eq!(page.next_cursor, Some(100));
+    assert_eq!(
+        client
+            .get_streams_by_receiver(&receiver, &100, &1000)
+            .stream_ids
+            .len(),
+        5
+    );
+}
+
+#[test]
+fn test_transfer_receiver_moves_stream_between_indexes() {
+    let env = Env::default();
+    let (client, sender, token) = setup_test(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let s0 = create(&client, &sender, &alice, &token);
+    let s1 = create(&client, &sender, &alice, &token);
+    let s2 = create(&client, &sender, &alice, &token);
+
+    client.transfer_receiver(&s0, &sender, &bob);
+
+    // The remaining entries keep their order
+    assert_eq!(
+        client.get_streams_by_receiver(&alice, &0, &10).stream_ids,
+        vec![&env, s1, s2]
+    );
+    assert_eq!(
+        client.get_streams_by_receiver(&bob, &0, &10).stream_ids,
+        vec![&env, s0]
+    );
+
+    client.transfer_receiver(&s1, &sender, &bob);
+    client.transfer_receiver(&s2, &sender, &bob);
+    assert_eq!(client.get_receiver_stream_count(&alice), 0);
+    assert_eq!(
+        client.get_streams_by_receiver(&bob, &0, &10).stream_ids,
+        vec![&env, s0, s1, s2]
+    );
+
+    // Sender index is unaffected
+    assert_eq!(client.get_sender_stream_count(&sender), 3);
+}
+
+#[test]
+fn test_remove_ignores_lists_that_do_not_hold_the_stream() {
+    let env = Env::default();
+    let (client, sender, token) = setup_test(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let s0 = create(&client, &sender, &alice, &token);
+    let s1 = create(&client, &sender, &bob, &token);
+
+    env.as_contract(&client.address, || {
+        // An owner with no list at all
+        index::remove(&env, StreamIndex::Receiver, &Address::generate(&env), s0);
+        // An owner whose list holds another stream in the same slot
+        index::remove(&env, StreamIndex::Receiver, &bob, s0);
+    });
+
+    assert_eq!(
+        client.get_streams_by_receiver(&alice, &0, &10).stream_ids,
+        vec![&env, s0]
+    );
+    assert_eq!(
+        client.get_streams_by_receiver(&bob, &0, &10).stream_ids,
+        vec![&env, s1]
+    );
+    assert_eq!(client.get_receiver_stream_count(&bob), 1);
+}
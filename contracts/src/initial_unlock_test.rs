@@ -0,0 +1,116 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::setup_test;
+use crate::types::{CurveType, NextUnlock, PeriodicSchedule, StreamOptions};
+use soroban_sdk::{testutils::Ledger, Env};
+
+#[test]
+fn test_initial_unlock_claimable_before_cliff() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+    let options = StreamOptions {
+        cliff_time: Some(50),
+        initial_unlock_bps: 2_000,
+        ..StreamOptions::new(&env)
+    };
+    let stream_id =
+        client.create_stream_with_options(&sender, &receiver, &token, &1000, &0, &100, &options);
+
+    assert_eq!(client.withdraw(&stream_id, &receiver), 200);
+
+    env.ledger().with_mut(|li| li.timestamp = 49);
+    assert_eq!(
+        client.try_withdraw(&stream_id, &receiver),
+        Err(Ok(Error::InsufficientBalance))
+    );
+
+    // Half of the remaining 800 has vested at the cliff
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 400);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 400);
+    assert_eq!(token_client.balance(&receiver), 1000);
+}
+
+#[test]
+fn test_remainder_follows_selected_curve() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+    let options = StreamOptions {
+        curve_type: CurveType::Exponential,
+        initial_unlock_bps: 5_000,
+        ..StreamOptions::new(&env)
+    };
+    let stream_id =
+        client.create_stream_with_options(&sender, &receiver, &token, &1000, &0, &100, &options);
+
+    // 500 up front, then 25% of the remaining 500 at the halfway point
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 625);
+}
+
+#[test]
+fn test_next_unlock_reports_initial_unlock() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+    let options = StreamOptions {
+        curve_type: CurveType::Periodic(PeriodicSchedule {
+            period_secs: 30,
+            tranches: 3,
+        }),
+        initial_unlock_bps: 1_000,
+        ..StreamOptions::new(&env)
+    };
+    let stream_id =
+        client.create_stream_with_options(&sender, &receiver, &token, &1000, &10, &100, &options);
+
+    assert_eq!(
+        client.get_next_unlock(&stream_id),
+        Some(NextUnlock {
+            timestamp: 10,
+            amount: 100,
+        })
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    assert_eq!(
+        client.get_next_unlock(&stream_id),
+        Some(NextUnlock {
+            timestamp: 40,
+            amount: 300,
+        })
+    );
+}
+
+#[test]
+fn test_cancel_at_start_pays_initial_unlock() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+    let options = StreamOptions {
+        initial_unlock_bps: 2_000,
+        ..StreamOptions::new(&env)
+    };
+    let stream_id =
+        client.create_stream_with_options(&sender, &receiver, &token, &1000, &0, &100, &options);
+
+    client.cancel(&stream_id, &sender);
+    assert_eq!(token_client.balance(&receiver), 200);
+    assert_eq!(token_client.balance(&sender), 9800);
+}
+
+#[test]
+fn test_initial_unlock_above_total_rejected() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+    let options = StreamOptions {
+        initial_unlock_bps: 10_001,
+        ..StreamOptions::new(&env)
+    };
+
+    assert_eq!(
+        client
+            .try_create_stream_with_options(&sender, &receiver, &token, &1000, &0, &100, &options),
+        Err(Ok(Error::InvalidInitialUnlock))
+    );
+}
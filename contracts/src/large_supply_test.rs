@@ -0,0 +1,78 @@
+#![cfg(test)]
+use crate::test_utils::setup_with_balance;
+use crate::types::{CurveType, Milestone, StreamOptions};
+use crate::StellarStreamContractClient;
+use soroban_sdk::{testutils::Ledger, token::TokenClient, vec, Address, Env};
+
+/// 10^12 tokens with 18 decimals: `total * elapsed^2` no longer fits in i128
+const SUPPLY: i128 = 1_000_000_000_000_000_000_000_000_000_000;
+
+fn setup_test(
+    env: &Env,
+) -> (
+    StellarStreamContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    TokenClient<'_>,
+) {
+    setup_with_balance(env, SUPPLY)
+}
+
+#[test]
+fn test_exponential_stays_on_curve_for_large_supply() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+
+    // A year-long stream, so elapsed^2 is large as well
+    let end = 31_536_000;
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token,
+        &SUPPLY,
+        &0,
+        &end,
+        &CurveType::Exponential,
+        &false,
+    );
+
+    // 70% of the way through: 49% unlocked, not the 70% a linear fallback gives
+    env.ledger().with_mut(|li| li.timestamp = end / 10 * 7);
+    assert_eq!(client.withdraw(&stream_id, &receiver), SUPPLY / 100 * 49);
+
+    env.ledger().with_mut(|li| li.timestamp = end);
+    client.withdraw(&stream_id, &receiver);
+    assert_eq!(token_client.balance(&receiver), SUPPLY);
+}
+
+#[test]
+fn test_milestones_and_linear_exact_for_large_supply() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+
+    let options = StreamOptions {
+        milestones: vec![
+            &env,
+            Milestone {
+                timestamp: 25,
+                percentage: 30,
+            },
+        ],
+        initial_unlock_bps: 1_000,
+        ..StreamOptions::new(&env)
+    };
+    let stream_id =
+        client.create_stream_with_options(&sender, &receiver, &token, &SUPPLY, &0, &100, &options);
+
+    // 10% up front, then of the remaining 90%: the 30% milestone plus half
+    // of the 70% curve share
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    let remainder = SUPPLY / 10 * 9;
+    let expected = SUPPLY / 10 + remainder / 100 * 30 + remainder / 100 * 70 / 2;
+    assert_eq!(client.withdraw(&stream_id, &receiver), expected);
+
+    client.cancel(&stream_id, &sender);
+    assert_eq!(token_client.balance(&receiver), expected);
+    assert_eq!(token_client.balance(&sender), SUPPLY - expected);
+}
@@ -3,6 +3,7 @@
 
 mod errors;
 mod flash_loan;
+mod index;
 mod interest;
 mod math;
 mod oracle;
@@ -15,17 +16,63 @@ mod voting;
 #[cfg(test)]
 mod allowlist_test;
 #[cfg(test)]
+mod amendment_test;
+#[cfg(test)]
+mod batch_test;
+#[cfg(test)]
+mod cancellation_test;
+#[cfg(test)]
 mod clawback_test;
 #[cfg(test)]
+mod cliff_test;
+#[cfg(test)]
+mod council_test;
+#[cfg(test)]
+mod custom_curve_test;
+#[cfg(test)]
+mod deposit_test;
+#[cfg(test)]
 mod dispute_test;
 #[cfg(test)]
+mod fee_test;
+#[cfg(test)]
+mod global_pause_test;
+#[cfg(test)]
+mod index_test;
+#[cfg(test)]
+mod initial_unlock_test;
+#[cfg(test)]
+mod large_supply_test;
+#[cfg(test)]
+mod milestone_test;
+#[cfg(test)]
+mod periodic_test;
+#[cfg(test)]
+mod power_curve_test;
+#[cfg(test)]
+mod proposal_test;
+#[cfg(test)]
+mod rate_stream_test;
+#[cfg(test)]
+mod rbac_test;
+#[cfg(test)]
+mod receipt_test;
+#[cfg(test)]
 mod soulbound_test;
 #[cfg(test)]
+mod storage_migration_test;
+#[cfg(test)]
+mod test_utils;
+#[cfg(test)]
+mod timelock_test;
+#[cfg(test)]
 mod topup_test;
 #[cfg(test)]
 mod vault_test;
 #[cfg(test)]
 mod voting_test;
+#[cfg(test)]
+mod withdraw_amount_test;
 
 // #[cfg(test)]
 // mod interest_test;
@@ -40,14 +87,28 @@ mod voting_test;
 mod ttl_stress_test;
 
 use errors::Error;
-use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env, Vec};
-use storage::{PROPOSAL_COUNT, RECEIPT, RESTRICTED_ADDRESSES, STREAM_COUNT};
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, symbol_short, token, Address, Env, Vec,
+};
+use storage::{
+    COUNCIL_COUNT, PROPOSAL_COUNT, RESTRICTED_ADDRESSES, STREAM_COUNT, STREAM_STORAGE_MIGRATION,
+};
 use types::{
-    ClawbackEvent, ContributorRequest, CurveType, DataKey, Milestone, ProposalApprovedEvent,
-    ProposalCreatedEvent, ReceiptMetadata, ReceiptTransferredEvent, RequestCreatedEvent,
-    RequestExecutedEvent, RequestKey, RequestStatus, Role, Stream, StreamCancelledEvent,
-    StreamClaimEvent, StreamCreatedEvent, StreamPausedEvent, StreamProposal, StreamReceipt,
-    StreamUnpausedEvent,
+    AddressRestrictedEvent, AmendmentProposedEvent, AmendmentTerms, CancelAuthority, ClawbackEvent,
+    ContributorRequest, Council, CouncilCreatedEvent, CouncilMember, CouncilUpdate,
+    CouncilUpdateCancelledEvent, CouncilUpdateProposedEvent, CouncilUpdatedEvent, CurvePoint,
+    CurveType, DataKey, DepositLiquidatedEvent, DepositSolvency, DisputeResolvedEvent,
+    FeeCollectedEvent, FeeMode, Milestone, NextUnlock, PauseState, PauseStateChangedEvent,
+    ProposalApprovedEvent, ProposalCancelledEvent, ProposalConfig, ProposalCreatedEvent,
+    ProposalPrunedEvent, ProposalQueuedEvent, ProposalRejectedEvent, ProposalVetoedEvent,
+    RateSchedule, ReceiptMetadata, ReceiptTransferredEvent, RequestCreatedEvent,
+    RequestExecutedEvent, RequestKey, RequestStatus, Role, SenderDeposit, Stream,
+    StreamAmendedEvent, StreamAmendment, StreamClaimEvent, StreamCreatedEvent, StreamFrozenEvent,
+    StreamIndex, StreamInsolventEvent, StreamOptions, StreamPage, StreamProposal, StreamRate,
+    StreamRateUpdatedEvent, StreamReceipt, StreamRequest, StreamSolventEvent, TopUpMode,
+    COUNCIL_UPDATE_LIFETIME, INTEREST_SPLIT_ALL, MAX_BATCH_SIZE, MAX_COUNCIL_MEMBERS,
+    MAX_CURVE_POINTS, MAX_DEPOSIT_STREAMS, MAX_FEE_BPS, MAX_POWER_EXPONENT, MIN_POWER_EXPONENT,
+    PROPOSAL_EXECUTION_WINDOW,
 };
 
 #[contract]
@@ -66,21 +127,94 @@ impl StellarStreamContract {
         required_approvals: u32,
         deadline: u64,
     ) -> Result<u64, Error> {
-        sender.require_auth();
+        let config = ProposalConfig {
+            required_approvals,
+            required_rejections: 0,
+            voters: Vec::new(&env),
+            deadline,
+            escrow: Self::default_proposal_timelock(&env) > 0,
+            council_id: None,
+            timelock: None,
+        };
+        let options = StreamOptions::new(&env);
+        Self::create_proposal_with_config(
+            env,
+            sender,
+            receiver,
+            token,
+            total_amount,
+            start_time,
+            end_time,
+            options,
+            config,
+        )
+    }
 
-        // Validate time range
-        if start_time >= end_time {
-            return Err(Error::InvalidTimeRange);
-        }
-        if total_amount <= 0 {
-            return Err(Error::InvalidAmount);
-        }
-        if required_approvals == 0 {
+    /// Create a proposal for a stream with the full `StreamOptions`, its
+    /// eligible voters and thresholds, and optionally funds locked up front.
+    /// Only listed voters can reject; `create_proposal` proposes a plain
+    /// linear stream that anyone can approve and nobody can reject, and pulls
+    /// the funds on execution, or up front when the global timelock is set.
+    /// Escrowed funds go back to the sender if the proposal is rejected,
+    /// cancelled or pruned. A proposal naming a council takes its approval
+    /// threshold from the council, fixed at creation, and is discarded once
+    /// the rejecting members leave too little weight to reach it.
+    ///
+    /// With a timelock, reaching quorum queues the proposal instead of
+    /// executing it. Anyone can then run it with `execute_queued_proposal`
+    /// once the delay has passed, unless the sender or a council member
+    /// vetoes it first. Timelocked proposals must be escrowed so the sender
+    /// can't block execution by moving the funds, and they expire
+    /// `PROPOSAL_EXECUTION_WINDOW` after the delay ends.
+    ///
+    /// Proposals are removed from storage once executed.
+    pub fn create_proposal_with_config(
+        env: Env,
+        sender: Address,
+        receiver: Address,
+        token: Address,
+        total_amount: i128,
+        start_time: u64,
+        end_time: u64,
+        options: StreamOptions,
+        config: ProposalConfig,
+    ) -> Result<u64, Error> {
+        sender.require_auth();
+        Self::require_creation_active(&env)?;
+
+        Self::validate_stream_options(&env, total_amount, start_time, end_time, &options)?;
+        let (required_approvals, required_rejections) = match config.council_id {
+            Some(council_id) => {
+                let council =
+                    storage::load_council(&env, council_id).ok_or(Error::CouncilNotFound)?;
+                Self::council_thresholds(&council)
+            }
+            None => (config.required_approvals, config.required_rejections),
+        };
+        let thresholds_valid = if config.council_id.is_some() {
+            required_approvals > 0 && required_rejections > 0
+        } else if config.voters.is_empty() {
+            required_approvals > 0 && required_rejections == 0
+        } else {
+            let voter_count = config.voters.len();
+            (1..=voter_count).contains(&required_approvals)
+                && (1..=voter_count).contains(&required_rejections)
+        };
+        if !thresholds_valid {
             return Err(Error::InvalidApprovalThreshold);
         }
-        if deadline <= env.ledger().timestamp() {
+        if config.deadline <= env.ledger().timestamp() {
             return Err(Error::ProposalExpired);
         }
+        let timelock = config
+            .timelock
+            .unwrap_or_else(|| Self::default_proposal_timelock(&env));
+        if timelock > 0 && !config.escrow {
+            return Err(Error::EscrowRequired);
+        }
+        if Self::is_restricted(&env, &receiver) {
+            return Err(Error::AddressRestricted);
+        }
 
         let proposal_id: u64 = env.storage().instance().get(&PROPOSAL_COUNT).unwrap_or(0);
         let next_id = proposal_id + 1;
@@ -92,15 +226,27 @@ impl StellarStreamContract {
             total_amount,
             start_time,
             end_time,
+            options,
             approvers: Vec::new(&env),
             required_approvals,
-            deadline,
-            executed: false,
+            rejectors: Vec::new(&env),
+            required_rejections,
+            approval_weight: 0,
+            rejection_weight: 0,
+            voters: config.voters,
+            council_id: config.council_id,
+            deadline: config.deadline,
+            timelock,
+            executable_at: None,
+            escrowed: config.escrow,
         };
 
-        env.storage()
-            .instance()
-            .set(&(PROPOSAL_COUNT, proposal_id), &proposal);
+        if config.escrow {
+            let token_client = token::Client::new(&env, &token);
+            token_client.transfer(&sender, &env.current_contract_address(), &total_amount);
+        }
+
+        storage::save_proposal(&env, proposal_id, &proposal);
         env.storage().instance().set(&PROPOSAL_COUNT, &next_id);
 
         // Emit ProposalCreatedEvent
@@ -115,7 +261,11 @@ impl StellarStreamContract {
                 start_time,
                 end_time,
                 required_approvals,
-                deadline,
+                required_rejections,
+                deadline: config.deadline,
+                escrowed: config.escrow,
+                council_id: config.council_id,
+                timelock,
                 timestamp: env.ledger().timestamp(),
             },
         );
@@ -125,16 +275,13 @@ impl StellarStreamContract {
 
     pub fn approve_proposal(env: Env, proposal_id: u64, approver: Address) -> Result<(), Error> {
         approver.require_auth();
+        Self::require_creation_active(&env)?;
 
-        let key = (PROPOSAL_COUNT, proposal_id);
-        let mut proposal: StreamProposal = env
-            .storage()
-            .instance()
-            .get(&key)
-            .ok_or(Error::ProposalNotFound)?;
+        let mut proposal =
+            storage::load_proposal(&env, proposal_id).ok_or(Error::ProposalNotFound)?;
 
-        if proposal.executed {
-            return Err(Error::ProposalAlreadyExecuted);
+        if proposal.executable_at.is_some() {
+            return Err(Error::ProposalQueued);
         }
         if env.ledger().timestamp() > proposal.deadline {
             return Err(Error::ProposalExpired);
@@ -145,16 +292,34 @@ impl StellarStreamContract {
                 return Err(Error::AlreadyApproved);
             }
         }
+        if proposal.rejectors.contains(&approver) {
+            return Err(Error::AlreadyRejected);
+        }
+        let weight = Self::vote_weight(&env, &proposal, &approver)?;
 
         proposal.approvers.push_back(approver.clone());
+        proposal.approval_weight += weight;
+        Self::recount_council_votes(&env, &mut proposal)?;
         let approval_count = proposal.approvers.len();
 
-        if approval_count >= proposal.required_approvals {
-            proposal.executed = true;
-            env.storage().instance().set(&key, &proposal);
-            Self::execute_proposal(&env, proposal.clone())?;
+        if proposal.approval_weight < proposal.required_approvals {
+            storage::save_proposal(&env, proposal_id, &proposal);
+        } else if proposal.timelock > 0 {
+            let executable_at = env.ledger().timestamp() + proposal.timelock;
+            proposal.executable_at = Some(executable_at);
+            storage::save_proposal(&env, proposal_id, &proposal);
+
+            env.events().publish(
+                (symbol_short!("queue"), proposal_id),
+                ProposalQueuedEvent {
+                    proposal_id,
+                    executable_at,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
         } else {
-            env.storage().instance().set(&key, &proposal);
+            storage::remove_proposal(&env, proposal_id);
+            Self::execute_proposal(&env, proposal.clone())?;
         }
 
         // Emit ProposalApprovedEvent
@@ -164,6 +329,7 @@ impl StellarStreamContract {
                 proposal_id,
                 approver: approver.clone(),
                 approval_count,
+                approval_weight: proposal.approval_weight,
                 required_approvals: proposal.required_approvals,
                 timestamp: env.ledger().timestamp(),
             },
@@ -172,71 +338,297 @@ impl StellarStreamContract {
         Ok(())
     }
 
-    fn execute_proposal(env: &Env, proposal: StreamProposal) -> Result<u64, Error> {
-        // Transfer tokens from proposer to contract
-        let token_client = token::Client::new(env, &proposal.token);
-        token_client.transfer(
-            &proposal.sender,
-            &env.current_contract_address(),
-            &proposal.total_amount,
+    /// Vote against a proposal. Only the proposal's listed voters or council
+    /// members can reject; once the rejections weigh `required_rejections`,
+    /// the proposal is discarded.
+    pub fn reject_proposal(env: Env, proposal_id: u64, rejector: Address) -> Result<(), Error> {
+        rejector.require_auth();
+
+        let mut proposal =
+            storage::load_proposal(&env, proposal_id).ok_or(Error::ProposalNotFound)?;
+
+        if proposal.executable_at.is_some() {
+            return Err(Error::ProposalQueued);
+        }
+        if env.ledger().timestamp() > proposal.deadline {
+            return Err(Error::ProposalExpired);
+        }
+        if proposal.approvers.contains(&rejector) {
+            return Err(Error::AlreadyApproved);
+        }
+        if proposal.rejectors.contains(&rejector) {
+            return Err(Error::AlreadyRejected);
+        }
+        if proposal.council_id.is_none() && proposal.voters.is_empty() {
+            return Err(Error::Unauthorized);
+        }
+        let weight = Self::vote_weight(&env, &proposal, &rejector)?;
+
+        proposal.rejectors.push_back(rejector.clone());
+        proposal.rejection_weight += weight;
+        Self::recount_council_votes(&env, &mut proposal)?;
+        let rejection_count = proposal.rejectors.len();
+
+        if proposal.rejection_weight >= proposal.required_rejections {
+            storage::remove_proposal(&env, proposal_id);
+            Self::refund_proposal(&env, &proposal);
+        } else {
+            storage::save_proposal(&env, proposal_id, &proposal);
+        }
+
+        env.events().publish(
+            (symbol_short!("reject"), rejector.clone()),
+            ProposalRejectedEvent {
+                proposal_id,
+                rejector,
+                rejection_count,
+                rejection_weight: proposal.rejection_weight,
+                required_rejections: proposal.required_rejections,
+                timestamp: env.ledger().timestamp(),
+            },
         );
 
-        // Allocate next stream id
-        let stream_id: u64 = env.storage().instance().get(&STREAM_COUNT).unwrap_or(0);
-        let next_id = stream_id + 1;
+        Ok(())
+    }
 
-        let stream = Stream {
-            sender: proposal.sender.clone(),
-            receiver: proposal.receiver.clone(),
-            token: proposal.token.clone(),
-            total_amount: proposal.total_amount,
-            start_time: proposal.start_time,
-            end_time: proposal.end_time,
-            withdrawn_amount: 0,
-            interest_strategy: 0,
-            vault_address: None,
-            deposited_principal: proposal.total_amount,
-            metadata: None,
-            withdrawn: 0,
-            cancelled: false,
-            receipt_owner: proposal.receiver.clone(),
-            is_paused: false,
-            paused_time: 0,
-            total_paused_duration: 0,
-            milestones: Vec::new(env),
-            curve_type: CurveType::Linear,
-            is_usd_pegged: false,
-            usd_amount: 0,
-            oracle_address: proposal.sender.clone(),
-            oracle_max_staleness: 0,
-            price_min: 0,
-            price_max: 0,
-            is_soulbound: false,     // Proposals default to non-soulbound
-            clawback_enabled: false, // Check at runtime if needed
-            arbiter: None,
-            is_frozen: false,
+    /// Withdraw a proposal that has not executed yet. Only its sender can.
+    pub fn cancel_proposal(env: Env, proposal_id: u64, sender: Address) -> Result<(), Error> {
+        sender.require_auth();
+
+        let proposal = storage::load_proposal(&env, proposal_id).ok_or(Error::ProposalNotFound)?;
+
+        if proposal.sender != sender {
+            return Err(Error::Unauthorized);
+        }
+
+        storage::remove_proposal(&env, proposal_id);
+        Self::refund_proposal(&env, &proposal);
+
+        env.events().publish(
+            (symbol_short!("p_cancel"), sender.clone()),
+            ProposalCancelledEvent {
+                proposal_id,
+                sender,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Remove proposals past their voting deadline, or queued proposals past
+    /// their execution window. Anyone can call this; ids that are missing or
+    /// still live are skipped, and executed proposals left from before the
+    /// proposal layout changed are cleared. Returns the number of proposals
+    /// removed.
+    pub fn prune_expired_proposals(env: Env, proposal_ids: Vec<u64>) -> u32 {
+        let now = env.ledger().timestamp();
+        let mut pruned = 0;
+
+        for proposal_id in proposal_ids.iter() {
+            let Some(proposal) = storage::load_proposal(&env, proposal_id) else {
+                continue;
+            };
+            let expires_at = Self::proposal_expiry(&proposal);
+            if now <= expires_at {
+                continue;
+            }
+
+            storage::remove_proposal(&env, proposal_id);
+            Self::refund_proposal(&env, &proposal);
+            pruned += 1;
+
+            env.events().publish(
+                (symbol_short!("prune"), proposal_id),
+                ProposalPrunedEvent {
+                    proposal_id,
+                    deadline: expires_at,
+                    timestamp: now,
+                },
+            );
+        }
+
+        pruned
+    }
+
+    /// Execute a queued proposal once its timelock has passed and before its
+    /// execution window closes. Anyone can call this; the funds are escrowed.
+    pub fn execute_queued_proposal(env: Env, proposal_id: u64) -> Result<u64, Error> {
+        Self::require_creation_active(&env)?;
+
+        let proposal = storage::load_proposal(&env, proposal_id).ok_or(Error::ProposalNotFound)?;
+
+        let executable_at = proposal.executable_at.ok_or(Error::ProposalNotQueued)?;
+        let now = env.ledger().timestamp();
+        if now < executable_at {
+            return Err(Error::TimelockActive);
+        }
+        if now > Self::proposal_expiry(&proposal) {
+            return Err(Error::ProposalExpired);
+        }
+
+        storage::remove_proposal(&env, proposal_id);
+        Self::execute_proposal(&env, proposal)
+    }
+
+    /// Stop a queued proposal during its timelock. The sender can veto, and
+    /// so can any current member of the proposal's council.
+    pub fn veto_proposal(env: Env, proposal_id: u64, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+
+        let proposal = storage::load_proposal(&env, proposal_id).ok_or(Error::ProposalNotFound)?;
+
+        if proposal.executable_at.is_none() {
+            return Err(Error::ProposalNotQueued);
+        }
+        let is_member = match proposal.council_id {
+            Some(council_id) => storage::load_council(&env, council_id)
+                .is_some_and(|council| Self::member_weight(&council, &caller).is_some()),
+            None => false,
         };
+        if caller != proposal.sender && !is_member {
+            return Err(Error::Unauthorized);
+        }
 
-        env.storage()
-            .instance()
-            .set(&(STREAM_COUNT, stream_id), &stream);
-        env.storage().instance().set(&STREAM_COUNT, &next_id);
+        storage::remove_proposal(&env, proposal_id);
+        Self::refund_proposal(&env, &proposal);
 
-        // Emit StreamCreatedEvent
         env.events().publish(
-            (symbol_short!("create"), proposal.sender.clone()),
-            StreamCreatedEvent {
-                stream_id,
-                sender: proposal.sender.clone(),
-                receiver: proposal.receiver.clone(),
-                token: proposal.token,
-                total_amount: proposal.total_amount,
-                start_time: proposal.start_time,
-                end_time: proposal.end_time,
+            (symbol_short!("veto"), caller.clone()),
+            ProposalVetoedEvent {
+                proposal_id,
+                vetoed_by: caller,
                 timestamp: env.ledger().timestamp(),
             },
         );
-        Self::mint_receipt(env, stream_id, &proposal.receiver);
+
+        Ok(())
+    }
+
+    /// Set the timelock for proposals that do not choose their own (Admin only)
+    pub fn set_proposal_timelock(env: Env, caller: Address, delay: u64) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_admin_active(&env)?;
+
+        if !Self::has_role(&env, &caller, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ProposalTimelock, &delay);
+        env.events()
+            .publish((symbol_short!("timelock"), caller), delay);
+
+        Ok(())
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Result<StreamProposal, Error> {
+        storage::load_proposal(&env, proposal_id).ok_or(Error::ProposalNotFound)
+    }
+
+    fn default_proposal_timelock(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ProposalTimelock)
+            .unwrap_or(0)
+    }
+
+    /// Last moment a proposal can still be voted on, or executed once queued
+    fn proposal_expiry(proposal: &StreamProposal) -> u64 {
+        match proposal.executable_at {
+            Some(executable_at) => executable_at.saturating_add(PROPOSAL_EXECUTION_WINDOW),
+            None => proposal.deadline,
+        }
+    }
+
+    /// Weight of a vote: the voter's current weight in the proposal's
+    /// council, or 1 for a listed voter or, without a list, anyone
+    fn vote_weight(env: &Env, proposal: &StreamProposal, voter: &Address) -> Result<u32, Error> {
+        let Some(council_id) = proposal.council_id else {
+            if !proposal.voters.is_empty() && !proposal.voters.contains(voter) {
+                return Err(Error::Unauthorized);
+            }
+            return Ok(1);
+        };
+        let council = storage::load_council(env, council_id).ok_or(Error::CouncilNotFound)?;
+        Self::member_weight(&council, voter).ok_or(Error::NotCouncilMember)
+    }
+
+    /// Combined weight needed to approve and to block a council proposal
+    fn council_thresholds(council: &Council) -> (u32, u32) {
+        let total_weight: u32 = council.members.iter().map(|m| m.weight).sum();
+        (council.threshold, total_weight - council.threshold + 1)
+    }
+
+    /// Re-tally a council proposal against the council's current membership.
+    /// A membership change while the proposal is open applies to its
+    /// thresholds and to every vote cast so far; removed members' votes no
+    /// longer count. No-op for proposals without a council.
+    fn recount_council_votes(env: &Env, proposal: &mut StreamProposal) -> Result<(), Error> {
+        let Some(council_id) = proposal.council_id else {
+            return Ok(());
+        };
+        let council = storage::load_council(env, council_id).ok_or(Error::CouncilNotFound)?;
+        let weight_of = |voters: &Vec<Address>| -> u32 {
+            voters
+                .iter()
+                .filter_map(|voter| Self::member_weight(&council, &voter))
+                .sum()
+        };
+
+        (proposal.required_approvals, proposal.required_rejections) =
+            Self::council_thresholds(&council);
+        proposal.approval_weight = weight_of(&proposal.approvers);
+        proposal.rejection_weight = weight_of(&proposal.rejectors);
+        Ok(())
+    }
+
+    /// Return escrowed funds of a proposal that will not execute
+    fn refund_proposal(env: &Env, proposal: &StreamProposal) {
+        if proposal.escrowed {
+            let token_client = token::Client::new(env, &proposal.token);
+            token_client.transfer(
+                &env.current_contract_address(),
+                &proposal.sender,
+                &proposal.total_amount,
+            );
+        }
+    }
+
+    fn execute_proposal(env: &Env, proposal: StreamProposal) -> Result<u64, Error> {
+        // The vault may have been delisted since the proposal was made
+        Self::validate_stream_options(
+            env,
+            proposal.total_amount,
+            proposal.start_time,
+            proposal.end_time,
+            &proposal.options,
+        )?;
+
+        // Transfer tokens from proposer to contract unless already escrowed
+        if !proposal.escrowed {
+            let token_client = token::Client::new(env, &proposal.token);
+            token_client.transfer(
+                &proposal.sender,
+                &env.current_contract_address(),
+                &proposal.total_amount,
+            );
+        }
+
+        let fee = Self::creation_fee(env, proposal.total_amount);
+        let stream_id = Self::store_new_stream(
+            env,
+            proposal.sender,
+            proposal.receiver,
+            proposal.token.clone(),
+            proposal.total_amount - fee,
+            proposal.start_time,
+            proposal.end_time,
+            proposal.options,
+            StreamRate::Scheduled,
+        )?;
+        Self::pay_fee(env, stream_id, &proposal.token, fee);
 
         Ok(stream_id)
     }
@@ -257,19 +649,20 @@ impl StellarStreamContract {
         curve_type: CurveType,
         is_soulbound: bool,
     ) -> Result<u64, Error> {
-        let milestones = Vec::new(&env);
-        Self::create_stream_with_milestones(
-            env,
+        let options = StreamOptions {
+            curve_type,
+            is_soulbound,
+            ..StreamOptions::new(&env)
+        };
+        Self::create_stream_internal(
+            &env,
             sender,
             receiver,
             token,
             total_amount,
             start_time,
             end_time,
-            milestones,
-            curve_type,
-            is_soulbound,
-            None, // No vault
+            options,
         )
     }
 
@@ -291,52 +684,381 @@ impl StellarStreamContract {
         is_soulbound: bool,
         vault_address: Option<Address>,
     ) -> Result<u64, Error> {
-        sender.require_auth();
-
-        // Validate time range
-        if start_time >= end_time {
-            return Err(Error::InvalidTimeRange);
-        }
-        if total_amount <= 0 {
-            return Err(Error::InvalidAmount);
-        }
-
-        // Validate vault if provided
-        let vault_shares = if let Some(ref vault) = vault_address {
-            // Check if vault is approved
-            if !Self::is_vault_approved(env.clone(), vault.clone()) {
-                return Err(Error::Unauthorized);
-            }
-
-            // Transfer tokens to contract first
-            let token_client = token::Client::new(&env, &token);
-            token_client.transfer(&sender, &env.current_contract_address(), &total_amount);
-
-            // Deposit to vault and get shares
-            vault::deposit_to_vault(&env, vault, &token, total_amount)
-                .map_err(|_| Error::InvalidAmount)?
-        } else {
-            // Standard stream without vault
-            let token_client = token::Client::new(&env, &token);
-            token_client.transfer(&sender, &env.current_contract_address(), &total_amount);
-            0
+        let options = StreamOptions {
+            milestones,
+            curve_type,
+            is_soulbound,
+            vault_address,
+            ..StreamOptions::new(&env)
+        };
+        Self::create_stream_internal(
+            &env,
+            sender,
+            receiver,
+            token,
+            total_amount,
+            start_time,
+            end_time,
+            options,
+        )
+    }
+
+    /// Create a new stream whose balance only becomes withdrawable at `cliff_time`
+    ///
+    /// # Parameters
+    /// - `cliff_time`: Must lie within `[start_time, end_time]`. Before the cliff nothing is
+    ///   withdrawable; at the cliff everything accrued along the curve unlocks at once.
+    /// - `is_soulbound`: Set to true to permanently bind this stream to the receiver's address.
+    ///   Cannot be changed after stream creation. Irreversible.
+    pub fn create_stream_with_cliff(
+        env: Env,
+        sender: Address,
+        receiver: Address,
+        token: Address,
+        total_amount: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        curve_type: CurveType,
+        is_soulbound: bool,
+    ) -> Result<u64, Error> {
+        let options = StreamOptions {
+            cliff_time: Some(cliff_time),
+            curve_type,
+            is_soulbound,
+            ..StreamOptions::new(&env)
+        };
+        Self::create_stream_internal(
+            &env,
+            sender,
+            receiver,
+            token,
+            total_amount,
+            start_time,
+            end_time,
+            options,
+        )
+    }
+
+    /// Create a new stream with any combination of cliff, milestones, curve,
+    /// soulbound locking, vault, interest strategy, metadata and cancellation policy
+    pub fn create_stream_with_options(
+        env: Env,
+        sender: Address,
+        receiver: Address,
+        token: Address,
+        total_amount: i128,
+        start_time: u64,
+        end_time: u64,
+        options: StreamOptions,
+    ) -> Result<u64, Error> {
+        Self::create_stream_internal(
+            &env,
+            sender,
+            receiver,
+            token,
+            total_amount,
+            start_time,
+            end_time,
+            options,
+        )
+    }
+
+    /// Create an open-ended stream that accrues `rate_per_second` from
+    /// `start_time`. Once the deposit runs out the stream keeps accruing as
+    /// debt, paid first from the next `top_up_stream`. The sender can change
+    /// the rate with `update_stream_rate`, but can't cancel while in debt.
+    pub fn create_rate_stream(
+        env: Env,
+        sender: Address,
+        receiver: Address,
+        token: Address,
+        rate_per_second: i128,
+        deposit: i128,
+        start_time: u64,
+    ) -> Result<u64, Error> {
+        sender.require_auth();
+        Self::require_creation_active(&env)?;
+
+        if rate_per_second <= 0 || deposit <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        // Accrual from a past start would be owed the moment the stream exists
+        if start_time < env.ledger().timestamp() {
+            return Err(Error::InvalidTimeRange);
+        }
+
+        let fee = Self::creation_fee(&env, deposit);
+        let total_amount = deposit - fee;
+        let end_time = math::calculate_depletion_time(total_amount, 0, rate_per_second, start_time);
+        Self::validate_stream_terms(&env, total_amount, start_time, start_time, end_time, &None)?;
+
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&sender, &env.current_contract_address(), &deposit);
+
+        let stream_id = Self::store_new_stream(
+            &env,
+            sender,
+            receiver,
+            token.clone(),
+            total_amount,
+            start_time,
+            end_time,
+            StreamOptions::new(&env),
+            StreamRate::PerSecond(RateSchedule {
+                rate_per_second,
+                checkpoint_time: start_time,
+                accrued: 0,
+                insolvent: false,
+            }),
+        )?;
+        Self::pay_fee(&env, stream_id, &token, fee);
+
+        Ok(stream_id)
+    }
+
+    /// Create many streams from one sender and token in a single call
+    ///
+    /// Every request is validated before any funds move, the summed amount is
+    /// pulled from the sender in one transfer, and all streams are created
+    /// atomically: if any request is invalid, no stream is created.
+    /// Every `StreamRequest` field is applied; since requests carry no curve
+    /// or soulbound flag, batch streams are linear and transferable. Use
+    /// `create_stream_with_options` for anything else.
+    ///
+    /// At most `MAX_BATCH_SIZE` requests fit in one call: one transaction
+    /// cannot write the ledger entries of hundreds of streams, so a payroll
+    /// run of that size cannot be created atomically. Larger runs take
+    /// several calls, each atomic on its own but not as a whole.
+    pub fn create_streams_batch(
+        env: Env,
+        sender: Address,
+        token: Address,
+        requests: Vec<StreamRequest>,
+    ) -> Result<Vec<u64>, Error> {
+        sender.require_auth();
+        Self::require_creation_active(&env)?;
+
+        if requests.is_empty() {
+            return Err(Error::InvalidAmount);
+        }
+        if requests.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchTooLarge);
+        }
+
+        let mut total_amount: i128 = 0;
+        for request in requests.iter() {
+            let options = Self::batch_stream_options(&env, &request);
+            Self::validate_stream_options(
+                &env,
+                request.amount,
+                request.start_time,
+                request.end_time,
+                &options,
+            )?;
+            if Self::is_restricted(&env, &request.receiver) {
+                return Err(Error::AddressRestricted);
+            }
+            total_amount = total_amount
+                .checked_add(request.amount)
+                .ok_or(Error::InvalidAmount)?;
+        }
+
+        // Single transfer for the whole batch
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&sender, &env.current_contract_address(), &total_amount);
+
+        let mut stream_ids = Vec::new(&env);
+        for request in requests.iter() {
+            let fee = Self::creation_fee(&env, request.amount);
+            let options = Self::batch_stream_options(&env, &request);
+            let stream_id = Self::store_new_stream(
+                &env,
+                sender.clone(),
+                request.receiver,
+                token.clone(),
+                request.amount - fee,
+                request.start_time,
+                request.end_time,
+                options,
+                StreamRate::Scheduled,
+            )?;
+            Self::pay_fee(&env, stream_id, &token, fee);
+            stream_ids.push_back(stream_id);
+        }
+
+        Ok(stream_ids)
+    }
+
+    fn batch_stream_options(env: &Env, request: &StreamRequest) -> StreamOptions {
+        StreamOptions {
+            // A zero cliff means the row has none
+            cliff_time: Some(request.cliff_time).filter(|&cliff| cliff != 0),
+            vault_address: request.vault_address.clone(),
+            interest_strategy: request.interest_strategy,
+            metadata: request.metadata.clone(),
+            ..StreamOptions::new(env)
+        }
+    }
+
+    fn create_stream_internal(
+        env: &Env,
+        sender: Address,
+        receiver: Address,
+        token: Address,
+        total_amount: i128,
+        start_time: u64,
+        end_time: u64,
+        options: StreamOptions,
+    ) -> Result<u64, Error> {
+        sender.require_auth();
+        Self::require_creation_active(env)?;
+        Self::validate_stream_options(env, total_amount, start_time, end_time, &options)?;
+
+        // Transfer tokens to contract first
+        let token_client = token::Client::new(env, &token);
+        token_client.transfer(&sender, &env.current_contract_address(), &total_amount);
+
+        let fee = Self::creation_fee(env, total_amount);
+        let stream_id = Self::store_new_stream(
+            env,
+            sender,
+            receiver,
+            token.clone(),
+            total_amount - fee,
+            start_time,
+            end_time,
+            options,
+            StreamRate::Scheduled,
+        )?;
+        Self::pay_fee(env, stream_id, &token, fee);
+
+        Ok(stream_id)
+    }
+
+    fn validate_stream_options(
+        env: &Env,
+        total_amount: i128,
+        start_time: u64,
+        end_time: u64,
+        options: &StreamOptions,
+    ) -> Result<(), Error> {
+        Self::validate_stream_terms(
+            env,
+            total_amount,
+            start_time,
+            options.cliff_time.unwrap_or(start_time),
+            end_time,
+            &options.vault_address,
+        )?;
+        Self::validate_milestones(&options.milestones, start_time, end_time)?;
+        Self::validate_curve(&options.curve_type, start_time, end_time)?;
+        if options.initial_unlock_bps > 10_000 {
+            return Err(Error::InvalidInitialUnlock);
+        }
+        if options.interest_strategy > INTEREST_SPLIT_ALL {
+            return Err(Error::InvalidInterestStrategy);
+        }
+        Ok(())
+    }
+
+    fn validate_stream_terms(
+        env: &Env,
+        total_amount: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        vault_address: &Option<Address>,
+    ) -> Result<(), Error> {
+        // Validate time range
+        if start_time >= end_time {
+            return Err(Error::InvalidTimeRange);
+        }
+        if cliff_time < start_time || cliff_time > end_time {
+            return Err(Error::InvalidTimeRange);
+        }
+        if total_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        // Check if vault is approved
+        if let Some(vault) = vault_address {
+            if !Self::is_vault_approved(env.clone(), vault.clone()) {
+                return Err(Error::Unauthorized);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Persist a validated stream whose funds are already held by the contract
+    fn store_new_stream(
+        env: &Env,
+        sender: Address,
+        receiver: Address,
+        token: Address,
+        total_amount: i128,
+        start_time: u64,
+        end_time: u64,
+        options: StreamOptions,
+        rate: StreamRate,
+    ) -> Result<u64, Error> {
+        if Self::is_restricted(env, &receiver) {
+            return Err(Error::AddressRestricted);
+        }
+        if !Self::is_token_allowed(env.clone(), token.clone()) {
+            return Err(Error::TokenNotAllowed);
+        }
+        let StreamOptions {
+            cliff_time,
+            milestones,
+            curve_type,
+            is_soulbound,
+            vault_address,
+            interest_strategy,
+            metadata,
+            cancellation,
+            initial_unlock_bps,
+            arbiter,
+        } = options;
+        let cliff_time = cliff_time.unwrap_or(start_time);
+
+        // Deposit to vault and get shares
+        let vault_shares = if let Some(ref vault) = vault_address {
+            vault::deposit_to_vault(env, vault, &token, total_amount)
+                .map_err(|_| Error::InvalidAmount)?
+        } else {
+            0
+        };
+
+        // Withdrawal fees are fixed at creation so later config changes never
+        // apply retroactively or charge a stream that already paid on creation
+        // Streams funded from a shared deposit move no funds on creation, so
+        // they always pay on withdrawal
+        let withdrawal_fee_bps = match (Self::get_fee_mode(env.clone()), &rate) {
+            (FeeMode::OnWithdrawal, _) | (FeeMode::OnCreation, StreamRate::FromDeposit(_)) => {
+                Self::get_fee_bps(env.clone())
+            }
+            (FeeMode::OnCreation, _) => 0,
         };
 
-        let stream_id: u64 = env.storage().instance().get(&STREAM_COUNT).unwrap_or(0);
-        let next_id = stream_id + 1;
-
+        let stream_id: u64 = env.storage().instance().get(&STREAM_COUNT).unwrap_or(0);
+        let next_id = stream_id + 1;
+
         let stream = Stream {
             sender: sender.clone(),
             receiver: receiver.clone(),
             token: token.clone(),
             total_amount,
             start_time,
+            cliff_time,
             end_time,
             withdrawn_amount: 0,
-            interest_strategy: 0,
+            withdrawal_fee_bps,
+            interest_strategy,
             vault_address: vault_address.clone(),
             deposited_principal: total_amount,
-            metadata: None,
+            metadata,
             withdrawn: 0,
             cancelled: false,
             receipt_owner: receiver.clone(),
@@ -353,24 +1075,24 @@ impl StellarStreamContract {
             price_max: 0,
             is_soulbound,
             clawback_enabled: false, // TODO: Check token flags
-            arbiter: None,
+            arbiter,
             is_frozen: false,
+            cancellation,
+            initial_unlock_bps,
+            rate,
+            carried_amount: 0,
         };
 
-        let stream_key = (STREAM_COUNT, stream_id);
-
         // Extend contract instance TTL to ensure long-term accessibility
-        Self::extend_contract_ttl(&env);
+        Self::extend_contract_ttl(env);
 
-        env.storage().instance().set(&stream_key, &stream);
+        storage::save_stream(env, stream_id, &stream);
         env.storage().instance().set(&STREAM_COUNT, &next_id);
+        index::add(env, StreamIndex::Sender, &sender, stream_id);
+        index::add(env, StreamIndex::Receiver, &receiver, stream_id);
 
         // Store vault shares if vault is used
-        if vault_shares > 0 {
-            env.storage()
-                .instance()
-                .set(&DataKey::VaultShares(stream_id), &vault_shares);
-        }
+        storage::save_vault_shares(env, stream_id, vault_shares);
 
         // If soulbound, emit event and add to index
         if is_soulbound {
@@ -384,7 +1106,7 @@ impl StellarStreamContract {
                 .storage()
                 .persistent()
                 .get(&DataKey::SoulboundStreams)
-                .unwrap_or(Vec::new(&env));
+                .unwrap_or(Vec::new(env));
             soulbound_streams.push_back(stream_id);
             env.storage()
                 .persistent()
@@ -404,7 +1126,7 @@ impl StellarStreamContract {
                 timestamp: env.ledger().timestamp(),
             },
         );
-        Self::mint_receipt(&env, stream_id, &receiver);
+        Self::mint_receipt(env, stream_id, &receiver);
 
         Ok(stream_id)
     }
@@ -427,74 +1149,132 @@ impl StellarStreamContract {
             .set(&DataKey::Role(admin.clone(), Role::TreasuryManager), &true);
     }
 
-    pub fn grant_role(env: Env, admin: Address, target: Address, role: Role) {
-        admin.require_auth();
+    fn mint_receipt(env: &Env, stream_id: u64, owner: &Address) {
+        let receipt = StreamReceipt {
+            stream_id,
+            owner: owner.clone(),
+            minted_at: env.ledger().timestamp(),
+        };
+        storage::save_receipt(env, stream_id, &receipt);
+    }
 
-        // Check if admin has Admin role
-        let has_admin_role: bool = env
-            .storage()
-            .instance()
-            .get(&DataKey::Role(admin, Role::Admin))
-            .unwrap_or(false);
+    fn extend_contract_ttl(env: &Env) {
+        storage::extend_instance_ttl(env);
+    }
 
-        if !has_admin_role {
-            panic!("Unauthorized");
-        }
+    pub fn get_stream(env: Env, stream_id: u64) -> Result<Stream, Error> {
+        storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)
+    }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Role(target, role), &true);
+    pub fn get_receipt(env: Env, stream_id: u64) -> Result<StreamReceipt, Error> {
+        storage::load_receipt(&env, stream_id).ok_or(Error::StreamNotFound)
     }
 
-    pub fn revoke_role(env: Env, admin: Address, target: Address, role: Role) {
-        admin.require_auth();
+    /// Balances behind a receipt, for wallets and marketplaces to display
+    pub fn get_receipt_metadata(env: Env, stream_id: u64) -> Result<ReceiptMetadata, Error> {
+        let stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+        let unlocked = Self::calculate_unlocked(&stream, env.ledger().timestamp());
+        Ok(ReceiptMetadata {
+            stream_id,
+            locked_balance: stream.total_amount - unlocked,
+            unlocked_balance: unlocked,
+            total_amount: stream.total_amount,
+            token: stream.token,
+        })
+    }
 
-        // Check if admin has Admin role
-        let has_admin_role: bool = env
-            .storage()
-            .instance()
-            .get(&DataKey::Role(admin, Role::Admin))
-            .unwrap_or(false);
+    /// Stream ids created by `sender`, reading `limit` (at most 100) slots
+    /// from `cursor`. Start at 0 and pass the returned `next_cursor` to fetch
+    /// the next page; streams moving between lists never shift the rest.
+    pub fn get_streams_by_sender(env: Env, sender: Address, cursor: u32, limit: u32) -> StreamPage {
+        index::page(&env, StreamIndex::Sender, &sender, cursor, limit)
+    }
+
+    /// Stream ids whose claim is currently held by `receiver`, paginated like
+    /// `get_streams_by_sender`
+    pub fn get_streams_by_receiver(
+        env: Env,
+        receiver: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> StreamPage {
+        index::page(&env, StreamIndex::Receiver, &receiver, cursor, limit)
+    }
+
+    pub fn get_sender_stream_count(env: Env, sender: Address) -> u32 {
+        index::len(&env, StreamIndex::Sender, &sender)
+    }
 
-        if !has_admin_role {
-            panic!("Unauthorized");
+    pub fn get_receiver_stream_count(env: Env, receiver: Address) -> u32 {
+        index::len(&env, StreamIndex::Receiver, &receiver)
+    }
+
+    /// Next tranche of a periodic stream: when it unlocks and how much it
+    /// releases. `None` for other curves and for streams that are paused,
+    /// cancelled or already fully unlocked.
+    pub fn get_next_unlock(env: Env, stream_id: u64) -> Result<Option<NextUnlock>, Error> {
+        let stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+        let schedule = match &stream.curve_type {
+            CurveType::Periodic(schedule) => schedule.clone(),
+            _ => return Ok(None),
+        };
+        if stream.cancelled || stream.is_paused {
+            return Ok(None);
         }
 
-        env.storage()
-            .instance()
-            .remove(&DataKey::Role(target, role));
+        // Paused time pushes the whole schedule, cliff included, back
+        let now = env.ledger().timestamp();
+        let shifted_start = stream.start_time + stream.total_paused_duration;
+        let next_tranche = now.saturating_sub(shifted_start) / schedule.period_secs + 1;
+        if next_tranche > schedule.tranches as u64 {
+            return Ok(None);
+        }
+
+        // Tranches reached before the cliff are all released at the cliff
+        let mut timestamp = shifted_start + next_tranche * schedule.period_secs;
+        let cliff = stream.cliff_time + stream.total_paused_duration;
+        if now < cliff && (timestamp < cliff || next_tranche > 1) {
+            timestamp = cliff;
+        }
+        if now < stream.start_time && stream.initial_unlock_bps > 0 {
+            timestamp = stream.start_time;
+        }
+
+        let amount =
+            Self::calculate_unlocked(&stream, timestamp) - Self::calculate_unlocked(&stream, now);
+        Ok(Some(NextUnlock { timestamp, amount }))
     }
 
-    pub fn check_role(env: Env, address: Address, role: Role) -> bool {
-        env.storage()
-            .instance()
-            .get(&DataKey::Role(address, role))
-            .unwrap_or(false)
+    /// What an open-ended stream owes its receiver and can pay out now
+    pub fn get_covered_debt(env: Env, stream_id: u64) -> Result<i128, Error> {
+        let stream = Self::load_rate_stream(&env, stream_id)?;
+        if stream.cancelled {
+            return Ok(0);
+        }
+        let now = env.ledger().timestamp();
+        Ok(Self::calculate_unlocked(&stream, now) - stream.withdrawn_amount)
     }
 
-    pub fn get_admin(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set")
+    /// What an open-ended stream owes its receiver beyond its balance, to be
+    /// paid from the sender's next deposit
+    pub fn get_uncovered_debt(env: Env, stream_id: u64) -> Result<i128, Error> {
+        let stream = Self::load_rate_stream(&env, stream_id)?;
+        Ok(Self::uncovered_debt(&stream, env.ledger().timestamp()))
     }
 
-    fn mint_receipt(env: &Env, stream_id: u64, owner: &Address) {
-        let receipt = StreamReceipt {
-            stream_id,
-            owner: owner.clone(),
-            minted_at: env.ledger().timestamp(),
-        };
-        env.storage()
-            .instance()
-            .set(&(RECEIPT, stream_id), &receipt);
+    /// When an open-ended stream's balance runs out, or ran out if it is
+    /// already in debt
+    pub fn get_depletion_time(env: Env, stream_id: u64) -> Result<u64, Error> {
+        Ok(Self::load_rate_stream(&env, stream_id)?.end_time)
     }
 
-    pub fn get_stream(env: Env, stream_id: u64) -> Result<Stream, Error> {
-        env.storage()
-            .instance()
-            .get(&(STREAM_COUNT, stream_id))
-            .ok_or(Error::StreamNotFound)
+    fn load_rate_stream(env: &Env, stream_id: u64) -> Result<Stream, Error> {
+        let stream = storage::load_stream(env, stream_id).ok_or(Error::StreamNotFound)?;
+        match stream.rate {
+            StreamRate::PerSecond(_) => Ok(stream),
+            StreamRate::FromDeposit(_) => Err(Error::DepositFundedStream),
+            StreamRate::Scheduled => Err(Error::NotRateStream),
+        }
     }
 
     pub fn get_soulbound_streams(env: Env) -> Vec<u64> {
@@ -504,6 +1284,8 @@ impl StellarStreamContract {
             .unwrap_or(Vec::new(&env))
     }
 
+    /// Redirect a stream to a new receiver (sender only). Once the receipt has been
+    /// transferred or sold, the claim belongs to its holder and can't be redirected
     pub fn transfer_receiver(
         env: Env,
         stream_id: u64,
@@ -511,13 +1293,9 @@ impl StellarStreamContract {
         new_receiver: Address,
     ) -> Result<(), Error> {
         caller.require_auth();
+        Self::require_admin_active(&env)?;
 
-        let stream_key = (STREAM_COUNT, stream_id);
-        let mut stream: Stream = env
-            .storage()
-            .instance()
-            .get(&stream_key)
-            .ok_or(Error::StreamNotFound)?;
+        let mut stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
 
         // SOULBOUND CHECK FIRST
         if stream.is_soulbound {
@@ -528,37 +1306,98 @@ impl StellarStreamContract {
         if stream.sender != caller {
             return Err(Error::Unauthorized);
         }
+        if stream.receipt_owner != stream.receiver {
+            return Err(Error::Unauthorized);
+        }
 
         if stream.cancelled {
             return Err(Error::AlreadyCancelled);
         }
+        if Self::is_restricted(&env, &new_receiver) {
+            return Err(Error::AddressRestricted);
+        }
 
-        // Update receiver
+        // Update receiver; the claim moves with it, so the receipt is re-minted
+        index::transfer(
+            &env,
+            StreamIndex::Receiver,
+            &stream.receipt_owner,
+            &new_receiver,
+            stream_id,
+        );
         stream.receiver = new_receiver.clone();
-        env.storage().instance().set(&stream_key, &stream);
+        stream.receipt_owner = new_receiver.clone();
+        storage::save_stream(&env, stream_id, &stream);
+        Self::clear_voting_delegate(&env, stream_id);
+        Self::mint_receipt(&env, stream_id, &new_receiver);
 
         Ok(())
     }
 
-    /// Top up an active stream with additional funds
-    pub fn top_up_stream(
+    /// Transfer the stream receipt, and with it the right to withdraw, to `to`
+    pub fn transfer_receipt(
+        env: Env,
+        stream_id: u64,
+        from: Address,
+        to: Address,
+    ) -> Result<(), Error> {
+        from.require_auth();
+        Self::require_admin_active(&env)?;
+
+        let mut stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+
+        if stream.is_soulbound {
+            return Err(Error::StreamIsSoulbound);
+        }
+        if stream.receipt_owner != from {
+            return Err(Error::NotReceiptOwner);
+        }
+        if Self::is_restricted(&env, &to) {
+            return Err(Error::AddressRestricted);
+        }
+
+        index::transfer(&env, StreamIndex::Receiver, &from, &to, stream_id);
+        stream.receipt_owner = to.clone();
+        storage::save_stream(&env, stream_id, &stream);
+        Self::clear_voting_delegate(&env, stream_id);
+
+        let mut receipt = storage::load_receipt(&env, stream_id).ok_or(Error::StreamNotFound)?;
+        receipt.owner = to.clone();
+        storage::save_receipt(&env, stream_id, &receipt);
+
+        env.events().publish(
+            (symbol_short!("transfer"), from.clone()),
+            ReceiptTransferredEvent {
+                stream_id,
+                from,
+                to,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Top up a stream with additional funds. `ExtendDuration` keeps the
+    /// stream's pace and pushes its end out; `IncreaseRate` keeps the end and
+    /// releases the funds over the remaining period. Neither lowers what has
+    /// already unlocked. A stream that has fully unlocked restarts as a new
+    /// tranche from now. The creation fee comes off `amount`.
+    pub fn top_up_stream(
         env: Env,
         stream_id: u64,
         sender: Address,
         amount: i128,
+        mode: TopUpMode,
     ) -> Result<(), Error> {
         sender.require_auth();
+        Self::require_creation_active(&env)?;
 
         if amount <= 0 {
             return Err(Error::InvalidAmount);
         }
 
-        let key = (STREAM_COUNT, stream_id);
-        let mut stream: Stream = env
-            .storage()
-            .instance()
-            .get(&key)
-            .ok_or(Error::StreamNotFound)?;
+        let mut stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
 
         if stream.sender != sender {
             return Err(Error::Unauthorized);
@@ -568,35 +1407,333 @@ impl StellarStreamContract {
             return Err(Error::AlreadyCancelled);
         }
 
+        // Added funds pay the creation fee like the initial deposit did
+        let fee = Self::creation_fee(&env, amount);
+        let added = amount - fee;
         let current_time = env.ledger().timestamp();
-        if current_time >= stream.end_time {
-            return Err(Error::StreamEnded);
+        match stream.rate {
+            StreamRate::Scheduled => {
+                Self::top_up_scheduled(&env, &mut stream, added, mode, current_time)?
+            }
+            StreamRate::PerSecond(_) => {
+                Self::top_up_rate_stream(&mut stream, added, mode, current_time)
+            }
+            StreamRate::FromDeposit(_) => return Err(Error::DepositFundedStream),
         }
+        let new_total = stream.total_amount;
+        let new_end_time = stream.end_time;
 
         // Transfer tokens from sender
         let token_client = token::Client::new(&env, &stream.token);
         token_client.transfer(&sender, &env.current_contract_address(), &amount);
+        Self::pay_fee(&env, stream_id, &stream.token, fee);
 
-        // Calculate new end time based on flow rate
-        let total_duration = stream.end_time.saturating_sub(stream.start_time);
-        let flow_rate = stream.total_amount / total_duration as i128;
-
-        let new_total = stream.total_amount + amount;
-        let additional_duration = amount / flow_rate;
-        let new_end_time = stream.end_time + additional_duration as u64;
-
-        stream.total_amount = new_total;
-        stream.end_time = new_end_time;
-        env.storage().instance().set(&key, &stream);
+        Self::sync_solvency(&env, stream_id, &mut stream, current_time);
+        storage::save_stream(&env, stream_id, &stream);
 
         env.events().publish(
             (symbol_short!("topup"), stream_id),
             types::StreamToppedUpEvent {
                 stream_id,
                 sender,
-                amount,
+                amount: added,
                 new_total,
                 new_end_time,
+                mode,
+                timestamp: current_time,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn top_up_scheduled(
+        env: &Env,
+        stream: &mut Stream,
+        amount: i128,
+        mode: TopUpMode,
+        now: u64,
+    ) -> Result<(), Error> {
+        let unlocked = Self::calculate_unlocked(stream, now);
+        let old_total = stream.total_amount;
+        let duration = stream.end_time - stream.start_time;
+        // Extend in proportion to the amount rather than through a
+        // per-second rate, which rounds to zero for small streams
+        let extension = math::mul_div_floor(duration as i128, amount, old_total)
+            .map_or(u64::MAX, |d| u64::try_from(d).unwrap_or(u64::MAX));
+
+        if unlocked >= old_total {
+            // Everything has unlocked: what was there stays claimable and the
+            // top-up vests as a new tranche on the same curve, at the same
+            // pace or over the length of the last schedule. Custom and
+            // periodic schedules always repeat over that length.
+            if stream.is_paused {
+                return Err(Error::StreamPaused);
+            }
+            let length = match (mode, &stream.curve_type) {
+                (_, CurveType::Custom(_) | CurveType::Periodic(_)) => duration,
+                (TopUpMode::ExtendDuration, _) => extension.max(1),
+                (TopUpMode::IncreaseRate, _) => duration,
+            };
+            stream.carried_amount = old_total;
+            stream.total_amount = old_total + amount;
+            stream.milestones = Vec::new(env);
+            stream.initial_unlock_bps = 0;
+            stream.total_paused_duration = 0;
+            Self::reschedule(stream, now, now.saturating_add(length));
+            stream.cliff_time = now;
+            return Ok(());
+        }
+
+        stream.total_amount = old_total + amount;
+        match mode {
+            TopUpMode::ExtendDuration => {
+                let furthest = stream.end_time.saturating_add(extension);
+                let end_time = Self::furthest_end(stream, now, unlocked, furthest);
+                Self::reschedule(stream, stream.start_time, end_time);
+            }
+            // Before the cliff nothing has vested yet, so the schedule can
+            // simply carry the larger total
+            TopUpMode::IncreaseRate if now >= stream.cliff_time + stream.total_paused_duration => {
+                Self::restart_from(env, stream, unlocked, now)?
+            }
+            TopUpMode::IncreaseRate => {}
+        }
+        Ok(())
+    }
+
+    /// Restart a stream at `now`: what has unlocked stays unlocked and the
+    /// rest of `total_amount` vests between `now` and the original end.
+    /// Custom curves continue with what is left of their breakpoints and
+    /// periodic streams restart at their last tranche. Exponential and power
+    /// curves start their shape over on the new window, so their pace drops
+    /// back (or, front-loaded, jumps up) to the curve's opening rate.
+    fn restart_from(env: &Env, stream: &mut Stream, unlocked: i128, now: u64) -> Result<(), Error> {
+        if stream.is_paused {
+            return Err(Error::StreamPaused);
+        }
+
+        // Paused time is folded into the new window
+        let paused = stream.total_paused_duration;
+        let elapsed = now - stream.start_time - paused;
+        let duration = stream.end_time - stream.start_time;
+        let mut start_time = now;
+        match &mut stream.curve_type {
+            CurveType::Custom(points) => {
+                *points = Self::remaining_points(env, points, elapsed, duration);
+            }
+            CurveType::Periodic(schedule) => {
+                let passed = elapsed / schedule.period_secs;
+                start_time = stream.start_time + paused + passed * schedule.period_secs;
+                schedule.tranches -= passed as u32;
+            }
+            _ => {}
+        }
+
+        // Reached milestones are part of what has unlocked. The rest are
+        // rescaled onto the remaining funds: what was left after the last
+        // reached step becomes 100%, so a pure step schedule stays one.
+        let mut reached: u32 = 0;
+        let mut milestones = Vec::new(env);
+        for milestone in stream.milestones.iter() {
+            if milestone.timestamp <= stream.start_time + elapsed {
+                reached = reached.max(milestone.percentage);
+            } else if reached < 100 {
+                milestones.push_back(Milestone {
+                    timestamp: milestone.timestamp + paused,
+                    percentage: (milestone.percentage - reached) * 100 / (100 - reached),
+                });
+            }
+        }
+
+        stream.carried_amount = unlocked;
+        stream.initial_unlock_bps = 0;
+        stream.milestones = milestones;
+        stream.end_time += paused;
+        stream.start_time = start_time;
+        stream.cliff_time = start_time;
+        stream.total_paused_duration = 0;
+        Ok(())
+    }
+
+    /// What is left of a custom curve after `elapsed`, rescaled to run from
+    /// 0 to 10_000 bps over the rest of the stream
+    fn remaining_points(
+        env: &Env,
+        points: &Vec<CurvePoint>,
+        elapsed: u64,
+        duration: u64,
+    ) -> Vec<CurvePoint> {
+        let reached_bps =
+            math::calculate_custom_unlocked(10_000, points, elapsed).unwrap_or(10_000);
+        let mut remaining = Vec::new(env);
+        // A curve that already reached 100% before its end leaves nothing to
+        // follow; release the rest linearly
+        if reached_bps >= 10_000 {
+            remaining.push_back(CurvePoint {
+                time_offset: duration - elapsed,
+                unlocked_bps: 10_000,
+            });
+            return remaining;
+        }
+
+        for point in points.iter() {
+            if point.time_offset <= elapsed {
+                continue;
+            }
+            let unlocked_bps = math::mul_div_floor(
+                point.unlocked_bps as i128 - reached_bps,
+                10_000,
+                10_000 - reached_bps,
+            )
+            .map_or(10_000, |bps| bps.max(0) as u32);
+            remaining.push_back(CurvePoint {
+                time_offset: point.time_offset - elapsed,
+                unlocked_bps,
+            });
+        }
+        remaining
+    }
+
+    fn top_up_rate_stream(stream: &mut Stream, amount: i128, mode: TopUpMode, now: u64) {
+        Self::checkpoint_rate(stream, now);
+        stream.total_amount += amount;
+        let StreamRate::PerSecond(rate) = &mut stream.rate else {
+            return;
+        };
+        // Still in debt: keep the time the balance ran out
+        if rate.accrued > stream.total_amount {
+            return;
+        }
+
+        // Raising the rate keeps the projected depletion time, if it is
+        // still ahead; otherwise the balance just lasts longer
+        if mode == TopUpMode::IncreaseRate && !stream.is_paused && stream.end_time > now {
+            let remaining = stream.total_amount - rate.accrued;
+            let seconds_left = (stream.end_time - now) as i128;
+            rate.rate_per_second = rate.rate_per_second.max(remaining / seconds_left);
+        }
+        stream.end_time = math::calculate_depletion_time(
+            stream.total_amount,
+            rate.accrued,
+            rate.rate_per_second,
+            rate.checkpoint_time,
+        );
+    }
+
+    /// Latest end up to `max_end` at which `stream`, with its new total,
+    /// still unlocks at least `unlocked` at `now`. Stretching a curve slows
+    /// it down, so for non-linear curves the full extension could take back
+    /// part of what has already unlocked.
+    fn furthest_end(stream: &Stream, now: u64, unlocked: i128, max_end: u64) -> u64 {
+        let keeps_unlocked = |end_time: u64| {
+            let mut trial = stream.clone();
+            Self::reschedule(&mut trial, stream.start_time, end_time);
+            Self::calculate_unlocked(&trial, now) >= unlocked
+        };
+        if keeps_unlocked(max_end) {
+            return max_end;
+        }
+
+        let (mut low, mut high) = (stream.end_time, max_end);
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if keeps_unlocked(mid) {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        low
+    }
+
+    /// Move a scheduled stream onto `start_time..end_time`, stretching the
+    /// breakpoints of custom curves with it. Periodic schedules stretch their
+    /// period and end on the last whole one.
+    fn reschedule(stream: &mut Stream, start_time: u64, end_time: u64) {
+        let old_duration = (stream.end_time - stream.start_time) as i128;
+        let new_duration = end_time - start_time;
+        let mut end_time = end_time;
+        match &mut stream.curve_type {
+            CurveType::Custom(points) => {
+                let mut stretched = Vec::new(points.env());
+                for point in points.iter() {
+                    let time_offset = math::mul_div_floor(
+                        point.time_offset as i128,
+                        new_duration as i128,
+                        old_duration,
+                    )
+                    .map_or(new_duration, |offset| offset as u64);
+                    stretched.push_back(CurvePoint {
+                        time_offset,
+                        ..point
+                    });
+                }
+                *points = stretched;
+            }
+            CurveType::Periodic(schedule) => {
+                let tranches = schedule.tranches as u64;
+                schedule.period_secs = (new_duration / tranches).max(1);
+                end_time = start_time.saturating_add(schedule.period_secs * tranches);
+            }
+            _ => {}
+        }
+        stream.start_time = start_time;
+        stream.end_time = end_time;
+    }
+
+    /// Change the per-second rate of an open-ended stream. What accrued
+    /// under the old rate stays claimable; the new rate applies from now.
+    pub fn update_stream_rate(
+        env: Env,
+        stream_id: u64,
+        sender: Address,
+        new_rate: i128,
+    ) -> Result<(), Error> {
+        sender.require_auth();
+        Self::require_creation_active(&env)?;
+
+        if new_rate <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+        if stream.sender != sender {
+            return Err(Error::Unauthorized);
+        }
+        if stream.cancelled {
+            return Err(Error::AlreadyCancelled);
+        }
+
+        let current_time = env.ledger().timestamp();
+        Self::checkpoint_rate(&mut stream, current_time);
+        let rate = match &mut stream.rate {
+            StreamRate::PerSecond(rate) => rate,
+            StreamRate::FromDeposit(_) => return Err(Error::DepositFundedStream),
+            StreamRate::Scheduled => return Err(Error::NotRateStream),
+        };
+        let old_rate = rate.rate_per_second;
+        rate.rate_per_second = new_rate;
+        // An indebted stream keeps the time its balance ran out
+        if rate.accrued <= stream.total_amount {
+            stream.end_time = math::calculate_depletion_time(
+                stream.total_amount,
+                rate.accrued,
+                new_rate,
+                rate.checkpoint_time,
+            );
+        }
+        let new_end_time = stream.end_time;
+        Self::sync_solvency(&env, stream_id, &mut stream, current_time);
+        storage::save_stream(&env, stream_id, &stream);
+
+        env.events().publish(
+            (symbol_short!("rate"), stream_id),
+            StreamRateUpdatedEvent {
+                stream_id,
+                old_rate,
+                new_rate,
+                new_end_time,
                 timestamp: current_time,
             },
         );
@@ -607,270 +1744,2039 @@ impl StellarStreamContract {
     pub fn pause_stream(env: Env, stream_id: u64, caller: Address) -> Result<(), Error> {
         caller.require_auth();
 
-        let key = (STREAM_COUNT, stream_id);
-        let mut stream: Stream = env
-            .storage()
-            .instance()
-            .get(&key)
-            .ok_or(Error::StreamNotFound)?;
+        let mut stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+
+        if stream.sender != caller {
+            return Err(Error::Unauthorized);
+        }
+        if stream.cancelled {
+            return Err(Error::AlreadyCancelled);
+        }
+        if stream.is_paused {
+            return Ok(());
+        }
+        // Its deposit would keep paying for the paused stream
+        if let StreamRate::FromDeposit(_) = stream.rate {
+            return Err(Error::DepositFundedStream);
+        }
+
+        let current_time = env.ledger().timestamp();
+        Self::checkpoint_rate(&mut stream, current_time);
+        Self::sync_solvency(&env, stream_id, &mut stream, current_time);
+        stream.is_paused = true;
+        stream.paused_time = current_time;
+        storage::save_stream(&env, stream_id, &stream);
+
+        Ok(())
+    }
+
+    pub fn unpause_stream(env: Env, stream_id: u64, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+
+        if stream.sender != caller {
+            return Err(Error::Unauthorized);
+        }
+        if stream.cancelled {
+            return Err(Error::AlreadyCancelled);
+        }
+        if !stream.is_paused {
+            return Ok(());
+        }
+
+        let current_time = env.ledger().timestamp();
+        let pause_duration = current_time - stream.paused_time;
+        stream.total_paused_duration += pause_duration;
+        stream.is_paused = false;
+        stream.paused_time = 0;
+
+        // Open-ended streams resume accruing from now on what they had
+        if let StreamRate::PerSecond(rate) = &mut stream.rate {
+            rate.checkpoint_time = rate.checkpoint_time.max(current_time);
+            if rate.accrued <= stream.total_amount {
+                stream.end_time = math::calculate_depletion_time(
+                    stream.total_amount,
+                    rate.accrued,
+                    rate.rate_per_second,
+                    rate.checkpoint_time,
+                );
+            }
+        }
+
+        storage::save_stream(&env, stream_id, &stream);
+
+        Ok(())
+    }
+
+    /// Withdraw everything available to the receipt owner
+    pub fn withdraw(env: Env, stream_id: u64, caller: Address) -> Result<i128, Error> {
+        let receiver = caller.clone();
+        Self::withdraw_internal(&env, stream_id, caller, None, receiver)
+    }
+
+    /// Withdraw part of the available balance and send it to `to`.
+    /// Fails with `InsufficientBalance` if `amount` exceeds what has unlocked.
+    pub fn withdraw_amount(
+        env: Env,
+        stream_id: u64,
+        caller: Address,
+        amount: i128,
+        to: Address,
+    ) -> Result<i128, Error> {
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Self::withdraw_internal(&env, stream_id, caller, Some(amount), to)
+    }
+
+    /// Pays `amount` (or everything available when `None`) to `to`, less any
+    /// withdrawal fee, and returns the gross amount taken from the stream
+    fn withdraw_internal(
+        env: &Env,
+        stream_id: u64,
+        caller: Address,
+        amount: Option<i128>,
+        to: Address,
+    ) -> Result<i128, Error> {
+        caller.require_auth();
+        Self::require_withdrawal_active(env)?;
+
+        let mut stream = storage::load_stream(env, stream_id).ok_or(Error::StreamNotFound)?;
+
+        if stream.receipt_owner != caller {
+            return Err(Error::NotReceiptOwner);
+        }
+        if Self::is_restricted(env, &to) {
+            return Err(Error::AddressRestricted);
+        }
+
+        if stream.cancelled {
+            return Err(Error::AlreadyCancelled);
+        }
+        if stream.is_paused {
+            return Err(Error::StreamPaused);
+        }
+        if stream.is_frozen {
+            return Err(Error::StreamFrozen);
+        }
+
+        let current_time = env.ledger().timestamp();
+        let unlocked = Self::unlocked_at(env, &stream, current_time);
+        let available = unlocked - stream.withdrawn_amount;
+        let to_withdraw = amount.unwrap_or(available);
+
+        if available <= 0 || to_withdraw > available {
+            return Err(Error::InsufficientBalance);
+        }
+
+        let received = Self::redeem_from_vault(env, stream_id, &mut stream, to_withdraw)?;
+        stream.withdrawn_amount += to_withdraw;
+        Self::sync_solvency(env, stream_id, &mut stream, current_time);
+        storage::save_stream(env, stream_id, &stream);
+
+        let fee = math::calculate_fee(to_withdraw, stream.withdrawal_fee_bps);
+        let token_client = token::Client::new(env, &stream.token);
+        token_client.transfer(&env.current_contract_address(), &to, &(received - fee));
+        Self::pay_fee(env, stream_id, &stream.token, fee);
+
+        env.events().publish(
+            (symbol_short!("claim"), caller.clone()),
+            StreamClaimEvent {
+                stream_id,
+                claimer: caller,
+                to,
+                amount: to_withdraw,
+                total_claimed: stream.withdrawn_amount,
+                timestamp: current_time,
+            },
+        );
+
+        Ok(to_withdraw)
+    }
+
+    pub fn cancel(env: Env, stream_id: u64, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_withdrawal_active(&env)?;
+
+        let mut stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+
+        let is_sender = stream.sender == caller;
+        let is_owner = stream.receipt_owner == caller;
+        if !is_sender && !is_owner {
+            return Err(Error::Unauthorized);
+        }
+        if stream.cancelled {
+            return Err(Error::AlreadyCancelled);
+        }
+        if stream.is_frozen {
+            return Err(Error::StreamFrozen);
+        }
+
+        let current_time = env.ledger().timestamp();
+        let permitted = match stream.cancellation.authority {
+            CancelAuthority::Nobody => false,
+            CancelAuthority::SenderOnly => is_sender,
+            CancelAuthority::ReceiverOnly => is_owner,
+            CancelAuthority::Both => true,
+        };
+        let window_closed = stream
+            .cancellation
+            .cancelable_until
+            .is_some_and(|until| current_time >= until);
+        if !permitted || window_closed {
+            return Err(Error::CancellationNotAllowed);
+        }
+
+        // What a deposit-funded stream has not drawn stays in the deposit
+        if let StreamRate::FromDeposit(_) = stream.rate {
+            let mut deposit = storage::load_deposit(&env, &stream.sender, &stream.token);
+            Self::settle_deposit(&mut deposit, current_time);
+            let owed = Self::stop_deposit_stream(stream_id, &mut stream, &mut deposit);
+            storage::save_deposit(&env, &stream.sender, &stream.token, &deposit);
+            storage::save_stream(&env, stream_id, &stream);
+            Self::pay_receiver(&env, stream_id, &stream, owed);
+            return Ok(());
+        }
+
+        // Only the receipt owner can forgive what an insolvent stream owes
+        if !is_owner && Self::uncovered_debt(&stream, current_time) > 0 {
+            return Err(Error::OutstandingDebt);
+        }
+        Self::clear_amendment(&env, stream_id, &stream);
+        let unlocked = Self::calculate_unlocked(&stream, current_time);
+        let to_receiver = unlocked - stream.withdrawn_amount;
+        let remaining = stream.total_amount - stream.withdrawn_amount;
+        // Yield on what is left goes back to the sender with the refund
+        let to_sender =
+            Self::redeem_from_vault(&env, stream_id, &mut stream, remaining)? - to_receiver;
+
+        stream.cancelled = true;
+        stream.withdrawn_amount = unlocked;
+        storage::save_stream(&env, stream_id, &stream);
+
+        Self::pay_receiver(&env, stream_id, &stream, to_receiver);
+        if to_sender > 0 {
+            let token_client = token::Client::new(&env, &stream.token);
+            token_client.transfer(&env.current_contract_address(), &stream.sender, &to_sender);
+        }
+
+        Ok(())
+    }
+
+    /// Send `amount` owed by a stream to its receipt owner, less the
+    /// stream's withdrawal fee
+    fn pay_receiver(env: &Env, stream_id: u64, stream: &Stream, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
+        let fee = math::calculate_fee(amount, stream.withdrawal_fee_bps);
+        let token_client = token::Client::new(env, &stream.token);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &stream.receipt_owner,
+            &(amount - fee),
+        );
+        Self::pay_fee(env, stream_id, &stream.token, fee);
+    }
+
+    /// Take `amount` of a vault stream's principal back from its vault and
+    /// return what the contract received for it, yield included. Funds added
+    /// after creation never entered the vault and are returned as they are.
+    fn redeem_from_vault(
+        env: &Env,
+        stream_id: u64,
+        stream: &mut Stream,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        let Some(vault_address) = stream.vault_address.clone() else {
+            return Ok(amount);
+        };
+        let shares = storage::load_vault_shares(env, stream_id);
+        let principal = amount.min(stream.deposited_principal);
+        if shares <= 0 || principal <= 0 {
+            return Ok(amount);
+        }
+
+        let redeemed = math::portion_floor(shares, principal, stream.deposited_principal);
+        let received = vault::withdraw_from_vault(env, &vault_address, redeemed)
+            .map_err(|_| Error::InsufficientBalance)?;
+        if received < principal {
+            return Err(Error::InsufficientBalance);
+        }
+        storage::save_vault_shares(env, stream_id, shares - redeemed);
+        stream.deposited_principal -= principal;
+
+        Ok(amount - principal + received)
+    }
+
+    pub fn get_vault_shares(env: Env, stream_id: u64) -> i128 {
+        storage::load_vault_shares(&env, stream_id)
+    }
+
+    /// Unlocked amount of any stream at `now`. Streams funded from a shared
+    /// deposit stop accruing when the deposit runs out.
+    fn unlocked_at(env: &Env, stream: &Stream, now: u64) -> i128 {
+        if let StreamRate::FromDeposit(_) = stream.rate {
+            let deposit = storage::load_deposit(env, &stream.sender, &stream.token);
+            let funded_until = math::calculate_funded_until(
+                deposit.balance,
+                deposit.outgoing_rate,
+                deposit.checkpoint_time,
+            );
+            return Self::calculate_unlocked(stream, now.min(funded_until));
+        }
+        Self::calculate_unlocked(stream, now)
+    }
+
+    /// Charge a shared deposit for what its streams drew up to `now`, or up
+    /// to the time it ran out, whichever came first
+    fn settle_deposit(deposit: &mut SenderDeposit, now: u64) {
+        let funded_until = math::calculate_funded_until(
+            deposit.balance,
+            deposit.outgoing_rate,
+            deposit.checkpoint_time,
+        );
+        let until = now.min(funded_until).max(deposit.checkpoint_time);
+        deposit.balance -= deposit.outgoing_rate * (until - deposit.checkpoint_time) as i128;
+        deposit.checkpoint_time = until;
+    }
+
+    /// Stop a stream funded from a settled `deposit` at the deposit's
+    /// checkpoint and return what its receiver is still owed
+    fn stop_deposit_stream(
+        stream_id: u64,
+        stream: &mut Stream,
+        deposit: &mut SenderDeposit,
+    ) -> i128 {
+        let streamed = Self::calculate_unlocked(stream, deposit.checkpoint_time);
+        let owed = streamed - stream.withdrawn_amount;
+        stream.total_amount = streamed;
+        stream.withdrawn_amount = streamed;
+        stream.cancelled = true;
+
+        if let StreamRate::FromDeposit(rate) = &stream.rate {
+            deposit.outgoing_rate -= rate.rate_per_second;
+        }
+        if let Some(slot) = deposit.streams.first_index_of(stream_id) {
+            deposit.streams.remove(slot);
+        }
+        owed
+    }
+
+    /// Lock in what an open-ended stream has accrued so far, so a change to
+    /// its rate or balance only applies from `now`. No-op for other streams.
+    fn checkpoint_rate(stream: &mut Stream, now: u64) {
+        let Some(streamed) = Self::rate_streamed(stream, now) else {
+            return;
+        };
+        if let StreamRate::PerSecond(rate) = &mut stream.rate {
+            rate.accrued = streamed;
+            rate.checkpoint_time = rate.checkpoint_time.max(now);
+        }
+    }
+
+    /// Everything an open-ended stream has streamed by `now`, debt included.
+    /// `None` for streams on a fixed schedule.
+    fn rate_streamed(stream: &Stream, now: u64) -> Option<i128> {
+        let (StreamRate::PerSecond(rate) | StreamRate::FromDeposit(rate)) = &stream.rate else {
+            return None;
+        };
+        if now < stream.start_time {
+            return Some(0);
+        }
+        if stream.is_paused {
+            return Some(rate.accrued);
+        }
+        Some(math::calculate_rate_streamed(
+            rate.accrued,
+            rate.rate_per_second,
+            rate.checkpoint_time,
+            now,
+        ))
+    }
+
+    /// Debt of an open-ended stream that its balance does not cover
+    fn uncovered_debt(stream: &Stream, now: u64) -> i128 {
+        if stream.cancelled {
+            return 0;
+        }
+        Self::rate_streamed(stream, now).map_or(0, |streamed| {
+            streamed.saturating_sub(stream.total_amount).max(0)
+        })
+    }
+
+    /// Record a change in whether an open-ended stream can cover what it
+    /// owes, and announce it. Streams only run dry with the passage of time,
+    /// so this is noticed on the next call that touches the stream.
+    fn sync_solvency(env: &Env, stream_id: u64, stream: &mut Stream, now: u64) {
+        let debt = Self::uncovered_debt(stream, now);
+        let end_time = stream.end_time;
+        let StreamRate::PerSecond(rate) = &mut stream.rate else {
+            return;
+        };
+        let insolvent = debt > 0;
+        if insolvent == rate.insolvent {
+            return;
+        }
+        rate.insolvent = insolvent;
+
+        if insolvent {
+            env.events().publish(
+                (symbol_short!("insolvent"), stream_id),
+                StreamInsolventEvent {
+                    stream_id,
+                    uncovered_debt: debt,
+                    depleted_at: end_time,
+                    timestamp: now,
+                },
+            );
+        } else {
+            env.events().publish(
+                (symbol_short!("solvent"), stream_id),
+                StreamSolventEvent {
+                    stream_id,
+                    new_end_time: end_time,
+                    timestamp: now,
+                },
+            );
+        }
+    }
+
+    fn calculate_unlocked(stream: &Stream, current_time: u64) -> i128 {
+        if current_time < stream.start_time {
+            return 0;
+        }
+
+        // Open-ended streams can only pay out what has been deposited. A
+        // shared deposit caps its streams through `unlocked_at` instead.
+        if let Some(streamed) = Self::rate_streamed(stream, current_time) {
+            return match stream.rate {
+                StreamRate::FromDeposit(_) => streamed,
+                _ => streamed.min(stream.total_amount),
+            };
+        }
+
+        let mut effective_time = current_time;
+        if stream.is_paused {
+            effective_time = stream.paused_time;
+        }
+
+        // Paused time delays the cliff, the curve and the end alike, so
+        // everything below runs on the clock with pauses taken out
+        let effective_elapsed = effective_time
+            .saturating_sub(stream.start_time)
+            .saturating_sub(stream.total_paused_duration);
+        let effective_now = stream.start_time + effective_elapsed;
+
+        // The initial unlock is claimable from start_time, and a restarted
+        // stream keeps its earlier tranches unlocked. Cliff, milestones and
+        // curve only apply to the vesting remainder: nothing vests before
+        // the cliff, and once it passes everything accrued so far becomes
+        // available at once.
+        math::calculate_unlocked_with_initial(
+            stream.total_amount,
+            stream.carried_amount,
+            stream.initial_unlock_bps,
+            stream.start_time,
+            stream.cliff_time,
+            stream.end_time,
+            effective_now,
+            |vesting_amount| Self::vested(stream, vesting_amount, effective_now),
+        )
+    }
+
+    /// How much of `vesting_amount` the stream's milestones and curve have
+    /// released by `effective_now`, a time with pauses already taken out
+    fn vested(stream: &Stream, vesting_amount: i128, effective_now: u64) -> i128 {
+        let effective_elapsed = (effective_now - stream.start_time) as i128;
+
+        // Milestone percentages are cumulative: once reached, a milestone's
+        // percentage is the share unlocked by then. Whatever share the last
+        // milestone leaves out keeps streaming along the curve, so a final
+        // milestone at 100% gives a pure step schedule.
+        let mut milestone_percentage: u32 = 0;
+        let mut reached_percentage: u32 = 0;
+        for milestone in stream.milestones.iter() {
+            milestone_percentage = milestone_percentage.max(milestone.percentage);
+            if milestone.timestamp <= effective_now {
+                reached_percentage = reached_percentage.max(milestone.percentage);
+            }
+        }
+
+        // Round both the step floor and the milestone share down so the
+        // curve portion absorbs any dust and the sum never exceeds the total
+        let step_unlocked = math::portion_floor(vesting_amount, reached_percentage as i128, 100);
+        let curve_amount =
+            vesting_amount - math::portion_floor(vesting_amount, milestone_percentage as i128, 100);
+
+        let duration = (stream.end_time - stream.start_time) as i128;
+        let linear_unlocked = math::portion_floor(curve_amount, effective_elapsed, duration);
+
+        // Calculate curve unlocked amount based on curve type. The curve
+        // helpers only fail on parameters rejected at creation; fall back to
+        // linear rather than trapping if one ever slips through.
+        let curve_unlocked = match &stream.curve_type {
+            CurveType::Linear => linear_unlocked,
+            CurveType::Exponential => math::calculate_exponential_unlocked(
+                curve_amount,
+                stream.start_time,
+                stream.end_time,
+                effective_now,
+            )
+            .unwrap_or(linear_unlocked),
+            CurveType::Custom(points) => {
+                math::calculate_custom_unlocked(curve_amount, points, effective_elapsed as u64)
+                    .unwrap_or(linear_unlocked)
+            }
+            CurveType::Periodic(schedule) => math::calculate_periodic_unlocked(
+                curve_amount,
+                schedule.period_secs,
+                schedule.tranches,
+                effective_elapsed as u64,
+            )
+            .unwrap_or(linear_unlocked),
+            CurveType::Power(exponent) => math::calculate_power_unlocked(
+                curve_amount,
+                stream.start_time,
+                stream.end_time,
+                effective_now,
+                *exponent,
+            )
+            .unwrap_or(linear_unlocked),
+        };
+
+        step_unlocked + curve_unlocked
+    }
+
+    /// Custom curves must rise monotonically from (0, 0) and finish at exactly
+    /// 10_000 bps at the stream's end, so the schedule always pays out in full.
+    /// Periodic schedules must cover the stream's duration exactly, and power
+    /// exponents must stay within `MIN_POWER_EXPONENT..=MAX_POWER_EXPONENT`.
+    fn validate_curve(curve: &CurveType, start_time: u64, end_time: u64) -> Result<(), Error> {
+        let points = match curve {
+            CurveType::Custom(points) => points,
+            CurveType::Power(exponent) => {
+                if !(MIN_POWER_EXPONENT..=MAX_POWER_EXPONENT).contains(exponent) {
+                    return Err(Error::InvalidCurve);
+                }
+                return Ok(());
+            }
+            CurveType::Periodic(schedule) => {
+                let covered = schedule.period_secs.checked_mul(schedule.tranches as u64);
+                if schedule.period_secs == 0 || covered != Some(end_time - start_time) {
+                    return Err(Error::InvalidCurve);
+                }
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
+        if points.is_empty() || points.len() > MAX_CURVE_POINTS {
+            return Err(Error::InvalidCurve);
+        }
+
+        let mut last: Option<CurvePoint> = None;
+        for point in points.iter() {
+            if point.unlocked_bps > 10_000 {
+                return Err(Error::InvalidCurve);
+            }
+            if let Some(prev) = &last {
+                if point.time_offset <= prev.time_offset || point.unlocked_bps < prev.unlocked_bps {
+                    return Err(Error::InvalidCurve);
+                }
+            }
+            last = Some(point);
+        }
+
+        match last {
+            Some(point)
+                if point.time_offset == end_time - start_time && point.unlocked_bps == 10_000 =>
+            {
+                Ok(())
+            }
+            _ => Err(Error::InvalidCurve),
+        }
+    }
+
+    /// Milestones must fall inside the stream window in date order, and
+    /// their cumulative percentages may only grow, up to 100% of the stream.
+    fn validate_milestones(
+        milestones: &Vec<Milestone>,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<(), Error> {
+        let mut previous = Milestone {
+            timestamp: start_time,
+            percentage: 0,
+        };
+        for milestone in milestones.iter() {
+            if milestone.timestamp < previous.timestamp
+                || milestone.timestamp > end_time
+                || milestone.percentage < previous.percentage
+                || milestone.percentage > 100
+            {
+                return Err(Error::InvalidMilestone);
+            }
+            previous = milestone;
+        }
+        Ok(())
+    }
+
+    // ========== RBAC Functions ==========
+
+    /// Grant a role to an address (Admin only)
+    pub fn grant_role(env: Env, admin: Address, target: Address, role: Role) -> Result<(), Error> {
+        admin.require_auth();
+
+        // Check if caller has Admin role
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        // Grant the role
+        env.storage()
+            .instance()
+            .set(&DataKey::Role(target.clone(), role.clone()), &true);
+
+        // Emit event
+        env.events().publish((symbol_short!("grant"), target), role);
+        Ok(())
+    }
+
+    /// Revoke a role from an address (Admin only)
+    pub fn revoke_role(env: Env, admin: Address, target: Address, role: Role) -> Result<(), Error> {
+        admin.require_auth();
+
+        // Check if caller has Admin role
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        // Revoke the role
+        env.storage()
+            .instance()
+            .remove(&DataKey::Role(target.clone(), role.clone()));
+
+        // Emit event
+        env.events()
+            .publish((symbol_short!("revoke"), target), role);
+        Ok(())
+    }
+
+    /// Check if an address has a specific role
+    pub fn check_role(env: Env, address: Address, role: Role) -> bool {
+        Self::has_role(&env, &address, role)
+    }
+
+    /// Internal helper to check if an address has a role
+    fn has_role(env: &Env, address: &Address, role: Role) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Role(address.clone(), role))
+            .unwrap_or(false)
+    }
+
+    // ========== Vault Functions ==========
+
+    /// Allow streams to deposit into a lending vault (Admin only)
+    pub fn approve_vault(env: Env, admin: Address, vault: Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut vaults = Self::approved_vaults(&env);
+        if vaults.contains(&vault) {
+            return Ok(());
+        }
+        vaults.push_back(vault.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::ApprovedVaults, &vaults);
+
+        env.events()
+            .publish((symbol_short!("vault"), symbol_short!("approve")), vault);
+
+        Ok(())
+    }
+
+    /// Stop new streams from using a lending vault (Admin only).
+    /// Streams already deposited keep their shares.
+    pub fn revoke_vault(env: Env, admin: Address, vault: Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut vaults = Self::approved_vaults(&env);
+        let Some(index) = vaults.first_index_of(&vault) else {
+            return Ok(());
+        };
+        vaults.remove(index);
+        env.storage()
+            .instance()
+            .set(&DataKey::ApprovedVaults, &vaults);
+
+        env.events()
+            .publish((symbol_short!("vault"), symbol_short!("revoke")), vault);
+
+        Ok(())
+    }
+
+    pub fn is_vault_approved(env: Env, vault: Address) -> bool {
+        Self::approved_vaults(&env).contains(&vault)
+    }
+
+    fn approved_vaults(env: &Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::ApprovedVaults)
+            .unwrap_or(Vec::new(env))
+    }
+
+    // ========== Token Allowlist Functions ==========
+
+    /// Restrict new streams to allowed tokens, or lift the restriction (Admin only)
+    pub fn set_allowlist_enabled(env: Env, admin: Address, enabled: bool) -> Result<(), Error> {
+        admin.require_auth();
+
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AllowlistEnabled, &enabled);
+
+        env.events().publish(
+            (symbol_short!("allowlist"), symbol_short!("enabled")),
+            enabled,
+        );
+
+        Ok(())
+    }
+
+    /// Allow streams to use `token` while the allowlist is enabled (Admin only)
+    pub fn add_allowed_token(env: Env, admin: Address, token: Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut tokens = Self::allowed_tokens(&env);
+        if tokens.contains(&token) {
+            return Ok(());
+        }
+        tokens.push_back(token.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::AllowedTokens, &tokens);
+
+        env.events()
+            .publish((symbol_short!("allowlist"), symbol_short!("add")), token);
+
+        Ok(())
+    }
+
+    /// Take `token` off the allowlist (Admin only). Existing streams are unaffected.
+    pub fn remove_allowed_token(env: Env, admin: Address, token: Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut tokens = Self::allowed_tokens(&env);
+        let Some(index) = tokens.first_index_of(&token) else {
+            return Ok(());
+        };
+        tokens.remove(index);
+        env.storage()
+            .instance()
+            .set(&DataKey::AllowedTokens, &tokens);
+
+        env.events()
+            .publish((symbol_short!("allowlist"), symbol_short!("remove")), token);
+
+        Ok(())
+    }
+
+    pub fn is_allowlist_enabled(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::AllowlistEnabled)
+            .unwrap_or(false)
+    }
+
+    /// Whether new streams can use `token`: always while the allowlist is
+    /// disabled, otherwise only if it was added
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        !Self::is_allowlist_enabled(env.clone()) || Self::allowed_tokens(&env).contains(&token)
+    }
+
+    fn allowed_tokens(env: &Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AllowedTokens)
+            .unwrap_or(Vec::new(env))
+    }
+
+    // ========== OFAC Compliance Functions ==========
+
+    /// Add an address to the restricted list (Admin only)
+    /// Restricted addresses cannot receive streams, receipts or withdrawals.
+    pub fn restrict_address(env: Env, admin: Address, target: Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut restricted = Self::get_restricted_addresses(env.clone());
+        if restricted.contains(&target) {
+            return Ok(());
+        }
+        restricted.push_back(target.clone());
+        env.storage()
+            .persistent()
+            .set(&RESTRICTED_ADDRESSES, &restricted);
+
+        env.events().publish(
+            (symbol_short!("restrict"), target.clone()),
+            AddressRestrictedEvent {
+                address: target,
+                restricted: true,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Remove an address from the restricted list (Admin only)
+    pub fn unrestrict_address(env: Env, admin: Address, target: Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut restricted = Self::get_restricted_addresses(env.clone());
+        let Some(index) = restricted.first_index_of(&target) else {
+            return Ok(());
+        };
+        restricted.remove(index);
+        env.storage()
+            .persistent()
+            .set(&RESTRICTED_ADDRESSES, &restricted);
+
+        env.events().publish(
+            (symbol_short!("restrict"), target.clone()),
+            AddressRestrictedEvent {
+                address: target,
+                restricted: false,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn is_address_restricted(env: Env, address: Address) -> bool {
+        Self::is_restricted(&env, &address)
+    }
+
+    pub fn get_restricted_addresses(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&RESTRICTED_ADDRESSES)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    fn is_restricted(env: &Env, address: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, Vec<Address>>(&RESTRICTED_ADDRESSES)
+            .is_some_and(|restricted| restricted.contains(address))
+    }
+
+    // ========== Dispute Resolution Functions ==========
+
+    /// Designate the third party who can freeze the stream and settle a
+    /// dispute over it (sender only). The arbiter can't be replaced once set.
+    pub fn set_arbiter(
+        env: Env,
+        stream_id: u64,
+        sender: Address,
+        arbiter: Address,
+    ) -> Result<(), Error> {
+        sender.require_auth();
+
+        let mut stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+
+        if stream.sender != sender || stream.arbiter.is_some() {
+            return Err(Error::Unauthorized);
+        }
+        if stream.cancelled {
+            return Err(Error::AlreadyCancelled);
+        }
+
+        stream.arbiter = Some(arbiter);
+        storage::save_stream(&env, stream_id, &stream);
+
+        Ok(())
+    }
+
+    /// Halt withdrawals and cancellation until the arbiter resolves the dispute
+    pub fn freeze_stream(env: Env, stream_id: u64, arbiter: Address) -> Result<(), Error> {
+        arbiter.require_auth();
+
+        let mut stream = Self::load_arbitrated_stream(&env, stream_id, &arbiter)?;
+        stream.is_frozen = true;
+        storage::save_stream(&env, stream_id, &stream);
+
+        env.events().publish(
+            (symbol_short!("freeze"), stream_id),
+            StreamFrozenEvent {
+                stream_id,
+                arbiter,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Close the stream, paying `split_bps` of everything it still holds to
+    /// the receipt owner and the rest back to the sender
+    pub fn resolve_dispute(
+        env: Env,
+        stream_id: u64,
+        arbiter: Address,
+        split_bps: u32,
+    ) -> Result<(), Error> {
+        arbiter.require_auth();
+
+        if split_bps > 10_000 {
+            return Err(Error::InvalidAmount);
+        }
+        let mut stream = Self::load_arbitrated_stream(&env, stream_id, &arbiter)?;
+
+        let remaining = Self::close_stream(&env, stream_id, &mut stream)?;
+        let to_receiver = math::portion_floor(remaining, split_bps as i128, 10_000);
+        let to_sender = remaining - to_receiver;
+
+        Self::pay_receiver(&env, stream_id, &stream, to_receiver);
+        if to_sender > 0 {
+            let token_client = token::Client::new(&env, &stream.token);
+            token_client.transfer(&env.current_contract_address(), &stream.sender, &to_sender);
+        }
+
+        env.events().publish(
+            (symbol_short!("resolve"), stream_id),
+            DisputeResolvedEvent {
+                stream_id,
+                arbiter,
+                to_sender,
+                to_receiver,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    fn load_arbitrated_stream(
+        env: &Env,
+        stream_id: u64,
+        arbiter: &Address,
+    ) -> Result<Stream, Error> {
+        let stream = storage::load_stream(env, stream_id).ok_or(Error::StreamNotFound)?;
+        if stream.arbiter.as_ref() != Some(arbiter) {
+            return Err(Error::Unauthorized);
+        }
+        if stream.cancelled {
+            return Err(Error::AlreadyCancelled);
+        }
+        Ok(stream)
+    }
+
+    /// Cancel a stream on a third party's authority and return everything
+    /// it still holds for its parties: the unwithdrawn balance, or what a
+    /// deposit-funded stream drew and has not paid out. Any debt an
+    /// open-ended stream ran up is dropped.
+    fn close_stream(env: &Env, stream_id: u64, stream: &mut Stream) -> Result<i128, Error> {
+        if let StreamRate::FromDeposit(_) = stream.rate {
+            let mut deposit = storage::load_deposit(env, &stream.sender, &stream.token);
+            Self::settle_deposit(&mut deposit, env.ledger().timestamp());
+            let owed = Self::stop_deposit_stream(stream_id, stream, &mut deposit);
+            storage::save_deposit(env, &stream.sender, &stream.token, &deposit);
+            storage::save_stream(env, stream_id, stream);
+            return Ok(owed);
+        }
+
+        Self::clear_amendment(env, stream_id, stream);
+        let unwithdrawn = stream.total_amount - stream.withdrawn_amount;
+        let remaining = Self::redeem_from_vault(env, stream_id, stream, unwithdrawn)?;
+        stream.cancelled = true;
+        stream.withdrawn_amount = stream.total_amount;
+        storage::save_stream(env, stream_id, stream);
+        Ok(remaining)
+    }
+
+    // ========== Regulatory Clawback Functions ==========
+
+    /// Cancel a stream and send everything it still holds to `issuer`
+    /// (ComplianceOfficer only). Irreversible.
+    pub fn governance_clawback(
+        env: Env,
+        stream_id: u64,
+        officer: Address,
+        issuer: Address,
+        reason: Option<soroban_sdk::BytesN<32>>,
+    ) -> Result<(), Error> {
+        officer.require_auth();
+
+        if !Self::has_role(&env, &officer, Role::ComplianceOfficer) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+        if stream.cancelled {
+            return Err(Error::AlreadyCancelled);
+        }
+
+        let amount_clawed = Self::close_stream(&env, stream_id, &mut stream)?;
+        if amount_clawed > 0 {
+            let token_client = token::Client::new(&env, &stream.token);
+            token_client.transfer(&env.current_contract_address(), &issuer, &amount_clawed);
+        }
+
+        env.events().publish(
+            (symbol_short!("clawback"), symbol_short!("security")),
+            ClawbackEvent {
+                stream_id,
+                officer,
+                amount_clawed,
+                issuer,
+                reason,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    // ========== Voting Rights Functions ==========
+
+    /// Voting power of a stream: what has unlocked and not been withdrawn
+    pub fn get_voting_power(env: Env, stream_id: u64) -> Result<i128, Error> {
+        let stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+        Ok(Self::voting_power(&env, &stream))
+    }
+
+    /// Let `delegate` vote with the stream's power (receipt owner only).
+    /// Delegating again replaces the previous delegate.
+    pub fn delegate_voting_power(
+        env: Env,
+        stream_id: u64,
+        caller: Address,
+        delegate: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+        if stream.receipt_owner != caller {
+            return Err(Error::NotReceiptOwner);
+        }
+        if stream.cancelled {
+            return Err(Error::AlreadyCancelled);
+        }
+
+        Self::clear_voting_delegate(&env, stream_id);
+        storage::save_voting_delegate(&env, stream_id, &delegate);
+        index::add(&env, StreamIndex::Delegate, &delegate, stream_id);
+
+        env.events()
+            .publish((symbol_short!("delegate"), stream_id), (caller, delegate));
+
+        Ok(())
+    }
+
+    pub fn get_voting_delegate(env: Env, stream_id: u64) -> Option<Address> {
+        storage::load_voting_delegate(&env, stream_id)
+    }
+
+    /// Combined voting power of every stream delegated to `delegate`
+    pub fn get_delegated_voting_power(env: Env, delegate: Address) -> i128 {
+        let mut total: i128 = 0;
+        let mut cursor = Some(0);
+        while let Some(start) = cursor {
+            let page = index::page(
+                &env,
+                StreamIndex::Delegate,
+                &delegate,
+                start,
+                index::MAX_PAGE_SIZE,
+            );
+            for stream_id in page.stream_ids.iter() {
+                if let Some(stream) = storage::load_stream(&env, stream_id) {
+                    total += Self::voting_power(&env, &stream);
+                }
+            }
+            cursor = page.next_cursor;
+        }
+        total
+    }
+
+    fn voting_power(env: &Env, stream: &Stream) -> i128 {
+        if stream.cancelled {
+            return 0;
+        }
+        let unlocked = Self::unlocked_at(env, stream, env.ledger().timestamp());
+        (unlocked - stream.withdrawn_amount).max(0)
+    }
+
+    /// A delegation belongs to the receipt owner who made it, so it ends
+    /// when the claim changes hands
+    fn clear_voting_delegate(env: &Env, stream_id: u64) {
+        if let Some(previous) = storage::load_voting_delegate(env, stream_id) {
+            index::remove(env, StreamIndex::Delegate, &previous, stream_id);
+            storage::remove_voting_delegate(env, stream_id);
+        }
+    }
+
+    // ========== Global Pause Functions ==========
+
+    /// Pause or resume every flow at once (Pauser only)
+    pub fn set_pause(env: Env, caller: Address, paused: bool) -> Result<(), Error> {
+        Self::set_pause_state(
+            env,
+            caller,
+            PauseState {
+                creation: paused,
+                withdrawal: paused,
+                admin: paused,
+            },
+        )
+    }
+
+    /// Set each circuit breaker switch independently (Pauser only)
+    /// e.g. stop deposits during an incident while users keep withdrawing.
+    pub fn set_pause_state(env: Env, caller: Address, state: PauseState) -> Result<(), Error> {
+        caller.require_auth();
+
+        if !Self::has_role(&env, &caller, Role::Pauser) {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage().instance().set(&DataKey::IsPaused, &state);
+
+        env.events().publish(
+            (symbol_short!("gpause"), caller.clone()),
+            PauseStateChangedEvent {
+                pauser: caller,
+                creation: state.creation,
+                withdrawal: state.withdrawal,
+                admin: state.admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_pause_state(env: Env) -> PauseState {
+        env.storage()
+            .instance()
+            .get(&DataKey::IsPaused)
+            .unwrap_or(PauseState {
+                creation: false,
+                withdrawal: false,
+                admin: false,
+            })
+    }
+
+    /// Stream creation, top-ups, rate changes, proposals and contributor requests
+    fn require_creation_active(env: &Env) -> Result<(), Error> {
+        if Self::get_pause_state(env.clone()).creation {
+            return Err(Error::ContractPaused);
+        }
+        Ok(())
+    }
+
+    /// Withdrawals and cancellation payouts
+    fn require_withdrawal_active(env: &Env) -> Result<(), Error> {
+        if Self::get_pause_state(env.clone()).withdrawal {
+            return Err(Error::ContractPaused);
+        }
+        Ok(())
+    }
+
+    /// Receiver transfers and fee configuration
+    fn require_admin_active(env: &Env) -> Result<(), Error> {
+        if Self::get_pause_state(env.clone()).admin {
+            return Err(Error::ContractPaused);
+        }
+        Ok(())
+    }
+
+    // ========== Contract Upgrade Functions ==========
+
+    /// Upgrade the contract to a new WASM hash
+    /// Only addresses with Admin role can perform this operation
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: soroban_sdk::BytesN<32>) {
+        admin.require_auth();
+
+        // Check if caller has Admin role
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return; // Error::Unauthorized;
+        }
+
+        // Update the contract WASM
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        // Emit upgrade event with new WASM hash
+        env.events()
+            .publish((symbol_short!("upgrade"), admin), new_wasm_hash);
+    }
+
+    /// Move streams and receipts created before per-stream storage out of the
+    /// contract instance, `limit` stream ids per call (Admin only).
+    /// Returns true once every legacy stream has been migrated. Streams that
+    /// are touched before then are migrated on first access.
+    pub fn migrate_stream_storage(env: Env, admin: Address, limit: u32) -> Result<bool, Error> {
+        admin.require_auth();
+
+        if !Self::has_role(&env, &admin, Role::Admin) {
+            return Err(Error::Unauthorized);
+        }
+        let done_key = DataKey::MigrationExecuted(STREAM_STORAGE_MIGRATION);
+        if env.storage().instance().has(&done_key) {
+            return Err(Error::AlreadyExecuted);
+        }
+
+        let cursor: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::StreamMigrationCursor)
+            .unwrap_or(0);
+        let stream_count: u64 = env.storage().instance().get(&STREAM_COUNT).unwrap_or(0);
+        let end = cursor.saturating_add(limit as u64).min(stream_count);
+
+        for stream_id in cursor..end {
+            storage::migrate_legacy_stream(&env, stream_id);
+        }
+
+        env.events()
+            .publish((symbol_short!("migrate"), admin), (cursor, end));
+
+        if end < stream_count {
+            env.storage()
+                .instance()
+                .set(&DataKey::StreamMigrationCursor, &end);
+            return Ok(false);
+        }
+
+        env.storage()
+            .instance()
+            .remove(&DataKey::StreamMigrationCursor);
+        env.storage().instance().set(&done_key, &true);
+        Ok(true)
+    }
+
+    /// Get the current admin address (for backward compatibility)
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set")
+    }
+
+    // ========== Protocol Fee Functions ==========
+
+    /// Configure protocol fees (TreasuryManager only, once)
+    /// Fees default to being taken on creation; see `update_fee_mode`.
+    pub fn initialize_fee(
+        env: Env,
+        admin: Address,
+        fee_bps: u32,
+        treasury: Address,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        if !Self::has_role(&env, &admin, Role::TreasuryManager) {
+            return Err(Error::Unauthorized);
+        }
+        if env.storage().instance().has(&DataKey::Treasury) {
+            return Err(Error::AlreadyInitialized);
+        }
+        if fee_bps > MAX_FEE_BPS {
+            return Err(Error::InvalidFee);
+        }
+
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeMode, &FeeMode::OnCreation);
+
+        env.events()
+            .publish((symbol_short!("fee_init"), admin), (fee_bps, treasury));
+
+        Ok(())
+    }
+
+    /// Update the fee rate in basis points (TreasuryManager only)
+    /// Capped at `MAX_FEE_BPS`.
+    pub fn update_fee(env: Env, caller: Address, fee_bps: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_admin_active(&env)?;
+
+        if !Self::has_role(&env, &caller, Role::TreasuryManager) {
+            return Err(Error::Unauthorized);
+        }
+        if fee_bps > MAX_FEE_BPS {
+            return Err(Error::InvalidFee);
+        }
+
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        env.events()
+            .publish((symbol_short!("fee_upd"), caller), fee_bps);
+
+        Ok(())
+    }
+
+    /// Update the address receiving protocol fees (TreasuryManager only)
+    pub fn update_treasury(env: Env, caller: Address, treasury: Address) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_admin_active(&env)?;
+
+        if !Self::has_role(&env, &caller, Role::TreasuryManager) {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        env.events()
+            .publish((symbol_short!("treasury"), caller), treasury);
+
+        Ok(())
+    }
+
+    /// Choose whether fees are taken from the deposit on creation or from each
+    /// withdrawal (TreasuryManager only). Only affects streams created afterwards.
+    pub fn update_fee_mode(env: Env, caller: Address, mode: FeeMode) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_admin_active(&env)?;
+
+        if !Self::has_role(&env, &caller, Role::TreasuryManager) {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage().instance().set(&DataKey::FeeMode, &mode);
+        env.events()
+            .publish((symbol_short!("fee_mode"), caller), mode);
+
+        Ok(())
+    }
+
+    pub fn get_fee_bps(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::FeeBps).unwrap_or(0)
+    }
+
+    pub fn get_fee_mode(env: Env) -> FeeMode {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeMode)
+            .unwrap_or(FeeMode::OnCreation)
+    }
+
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Treasury)
+    }
+
+    /// Total fees paid to the treasury in `token` since deployment
+    pub fn get_collected_fees(env: Env, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CollectedFees(token))
+            .unwrap_or(0)
+    }
+
+    /// Fee owed on a new deposit when fees are charged on creation
+    fn creation_fee(env: &Env, amount: i128) -> i128 {
+        if Self::get_treasury(env.clone()).is_none() {
+            return 0;
+        }
+        match Self::get_fee_mode(env.clone()) {
+            FeeMode::OnCreation => math::calculate_fee(amount, Self::get_fee_bps(env.clone())),
+            FeeMode::OnWithdrawal => 0,
+        }
+    }
+
+    /// Send a fee held by the contract to the treasury and record it
+    fn pay_fee(env: &Env, stream_id: u64, token_address: &Address, fee: i128) {
+        if fee <= 0 {
+            return;
+        }
+        let treasury = match Self::get_treasury(env.clone()) {
+            Some(treasury) => treasury,
+            None => return,
+        };
+
+        let token_client = token::Client::new(env, token_address);
+        token_client.transfer(&env.current_contract_address(), &treasury, &fee);
+
+        let key = DataKey::CollectedFees(token_address.clone());
+        let collected: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(collected + fee));
+
+        env.events().publish(
+            (symbol_short!("fee"), stream_id),
+            FeeCollectedEvent {
+                stream_id,
+                token: token_address.clone(),
+                amount: fee,
+                treasury,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    // --- APPROVER COUNCILS ---
+
+    /// Store a council of weighted approvers for proposals to reference.
+    /// Later membership changes need the council's own approval.
+    pub fn create_council(
+        env: Env,
+        creator: Address,
+        members: Vec<CouncilMember>,
+        threshold: u32,
+    ) -> Result<u64, Error> {
+        creator.require_auth();
+        Self::validate_council(&members, threshold)?;
+
+        let council_id: u64 = env.storage().instance().get(&COUNCIL_COUNT).unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&COUNCIL_COUNT, &(council_id + 1));
+        storage::save_council(
+            &env,
+            council_id,
+            &Council {
+                members: members.clone(),
+                threshold,
+            },
+        );
+
+        env.events().publish(
+            (symbol_short!("council"), council_id),
+            CouncilCreatedEvent {
+                council_id,
+                creator,
+                members,
+                threshold,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(council_id)
+    }
+
+    /// Propose new members and threshold for a council. The proposer must be
+    /// a member and their approval counts. Only one change can be pending at
+    /// a time: it stays open for `COUNCIL_UPDATE_LIFETIME` unless its
+    /// proposer cancels it first.
+    pub fn propose_council_update(
+        env: Env,
+        council_id: u64,
+        proposer: Address,
+        members: Vec<CouncilMember>,
+        threshold: u32,
+    ) -> Result<(), Error> {
+        proposer.require_auth();
+
+        let council = storage::load_council(&env, council_id).ok_or(Error::CouncilNotFound)?;
+        if Self::member_weight(&council, &proposer).is_none() {
+            return Err(Error::NotCouncilMember);
+        }
+        if Self::pending_council_update(&env, council_id).is_some() {
+            return Err(Error::CouncilUpdatePending);
+        }
+        Self::validate_council(&members, threshold)?;
+
+        let update = CouncilUpdate {
+            proposer: proposer.clone(),
+            members: members.clone(),
+            threshold,
+            approvers: Vec::from_array(&env, [proposer.clone()]),
+            expires_at: env.ledger().timestamp() + COUNCIL_UPDATE_LIFETIME,
+        };
+
+        env.events().publish(
+            (symbol_short!("cncl_prop"), council_id),
+            CouncilUpdateProposedEvent {
+                council_id,
+                proposer,
+                members,
+                threshold,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Self::apply_council_update(&env, council_id, &council, update);
+        Ok(())
+    }
+
+    /// Approve a council's pending membership change as a current member
+    pub fn approve_council_update(
+        env: Env,
+        council_id: u64,
+        approver: Address,
+    ) -> Result<(), Error> {
+        approver.require_auth();
+
+        let council = storage::load_council(&env, council_id).ok_or(Error::CouncilNotFound)?;
+        let mut update =
+            Self::pending_council_update(&env, council_id).ok_or(Error::NoPendingCouncilUpdate)?;
+        if Self::member_weight(&council, &approver).is_none() {
+            return Err(Error::NotCouncilMember);
+        }
+        if update.approvers.contains(&approver) {
+            return Err(Error::AlreadyApproved);
+        }
+
+        update.approvers.push_back(approver);
+        Self::apply_council_update(&env, council_id, &council, update);
+        Ok(())
+    }
+
+    pub fn get_council(env: Env, council_id: u64) -> Result<Council, Error> {
+        storage::load_council(&env, council_id).ok_or(Error::CouncilNotFound)
+    }
+
+    /// Withdraw a council's pending membership change. Only its proposer can.
+    pub fn cancel_council_update(env: Env, council_id: u64, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+
+        let update =
+            Self::pending_council_update(&env, council_id).ok_or(Error::NoPendingCouncilUpdate)?;
+        if update.proposer != caller {
+            return Err(Error::Unauthorized);
+        }
+
+        storage::remove_council_update(&env, council_id);
+        env.events().publish(
+            (symbol_short!("cncl_canc"), council_id),
+            CouncilUpdateCancelledEvent {
+                council_id,
+                proposer: caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_council_update(env: Env, council_id: u64) -> Option<CouncilUpdate> {
+        Self::pending_council_update(&env, council_id)
+    }
+
+    /// A council's membership change that is still open for approval
+    fn pending_council_update(env: &Env, council_id: u64) -> Option<CouncilUpdate> {
+        storage::load_council_update(env, council_id)
+            .filter(|update| env.ledger().timestamp() <= update.expires_at)
+    }
+
+    /// Replace the council's membership once the approvers of `update` carry
+    /// its threshold, otherwise store the update as pending
+    fn apply_council_update(env: &Env, council_id: u64, council: &Council, update: CouncilUpdate) {
+        let approved_weight: u32 = update
+            .approvers
+            .iter()
+            .filter_map(|approver| Self::member_weight(council, &approver))
+            .sum();
+        if approved_weight < council.threshold {
+            storage::save_council_update(env, council_id, &update);
+            return;
+        }
+
+        storage::remove_council_update(env, council_id);
+        storage::save_council(
+            env,
+            council_id,
+            &Council {
+                members: update.members.clone(),
+                threshold: update.threshold,
+            },
+        );
+
+        env.events().publish(
+            (symbol_short!("cncl_upd"), council_id),
+            CouncilUpdatedEvent {
+                council_id,
+                members: update.members,
+                threshold: update.threshold,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    fn member_weight(council: &Council, address: &Address) -> Option<u32> {
+        council
+            .members
+            .iter()
+            .find(|m| m.member == *address)
+            .map(|m| m.weight)
+    }
 
-        if stream.sender != caller {
-            return Err(Error::Unauthorized);
+    fn validate_council(members: &Vec<CouncilMember>, threshold: u32) -> Result<(), Error> {
+        if members.is_empty() || members.len() > MAX_COUNCIL_MEMBERS {
+            return Err(Error::InvalidCouncil);
         }
-        if stream.cancelled {
-            return Err(Error::AlreadyCancelled);
+        let mut total_weight: u32 = 0;
+        for (i, member) in members.iter().enumerate() {
+            if member.weight == 0 {
+                return Err(Error::InvalidCouncil);
+            }
+            if members
+                .iter()
+                .skip(i + 1)
+                .any(|other| other.member == member.member)
+            {
+                return Err(Error::InvalidCouncil);
+            }
+            total_weight = total_weight
+                .checked_add(member.weight)
+                .ok_or(Error::InvalidCouncil)?;
         }
-        if stream.is_paused {
-            return Ok(());
+        if threshold == 0 || threshold > total_weight {
+            return Err(Error::InvalidCouncil);
         }
-
-        stream.is_paused = true;
-        stream.paused_time = env.ledger().timestamp();
-        env.storage().instance().set(&key, &stream);
-
         Ok(())
     }
 
-    pub fn unpause_stream(env: Env, stream_id: u64, caller: Address) -> Result<(), Error> {
-        caller.require_auth();
+    // --- STREAM AMENDMENTS ---
 
-        let key = (STREAM_COUNT, stream_id);
-        let mut stream: Stream = env
-            .storage()
-            .instance()
-            .get(&key)
-            .ok_or(Error::StreamNotFound)?;
+    /// Propose new terms for a stream. Either the sender or the receipt owner
+    /// proposes and the other accepts with `accept_amendment`; a new proposal
+    /// replaces the pending one. A sender proposing a larger total pays the
+    /// difference in with the proposal.
+    pub fn propose_amendment(
+        env: Env,
+        stream_id: u64,
+        proposer: Address,
+        terms: AmendmentTerms,
+    ) -> Result<(), Error> {
+        proposer.require_auth();
+        Self::require_admin_active(&env)?;
 
-        if stream.sender != caller {
+        let stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+        let is_sender = stream.sender == proposer;
+        if !is_sender && stream.receipt_owner != proposer {
             return Err(Error::Unauthorized);
         }
-        if stream.cancelled {
-            return Err(Error::AlreadyCancelled);
+        Self::require_amendable(&stream)?;
+
+        let now = env.ledger().timestamp();
+        if terms.total_amount < Self::calculate_unlocked(&stream, now)
+            || terms.end_time <= now
+            || !(now..=terms.end_time).contains(&terms.cliff_time)
+        {
+            return Err(Error::InvalidAmendment);
         }
-        if !stream.is_paused {
-            return Ok(());
+        Self::validate_curve(&terms.curve_type, now, terms.end_time)?;
+        if terms.receiver != stream.receipt_owner {
+            if stream.is_soulbound {
+                return Err(Error::StreamIsSoulbound);
+            }
+            if Self::is_restricted(&env, &terms.receiver) {
+                return Err(Error::AddressRestricted);
+            }
         }
 
-        let current_time = env.ledger().timestamp();
-        let pause_duration = current_time - stream.paused_time;
-        stream.total_paused_duration += pause_duration;
-        stream.is_paused = false;
-        stream.paused_time = 0;
+        Self::clear_amendment(&env, stream_id, &stream);
+        let escrowed = if is_sender {
+            let increase = (terms.total_amount - stream.total_amount).max(0);
+            increase + Self::creation_fee(&env, increase)
+        } else {
+            0
+        };
+        if escrowed > 0 {
+            let token_client = token::Client::new(&env, &stream.token);
+            token_client.transfer(&proposer, &env.current_contract_address(), &escrowed);
+        }
+
+        storage::save_amendment(
+            &env,
+            stream_id,
+            &StreamAmendment {
+                proposer: proposer.clone(),
+                terms: terms.clone(),
+                proposed_at: now,
+                escrowed,
+            },
+        );
 
-        env.storage().instance().set(&key, &stream);
+        env.events().publish(
+            (symbol_short!("amend"), stream_id),
+            AmendmentProposedEvent {
+                stream_id,
+                proposer,
+                terms,
+                timestamp: now,
+            },
+        );
 
         Ok(())
     }
 
-    pub fn withdraw(env: Env, stream_id: u64, caller: Address) -> Result<i128, Error> {
-        caller.require_auth();
+    /// Accept the pending amendment of a stream. `terms` must match what was
+    /// proposed, so the proposer cannot swap them at the last moment.
+    /// Whatever has unlocked stays claimable; the rest of the new total
+    /// follows the new schedule from now. A lower total is refunded to the
+    /// sender, and a sender accepting a higher one pays the difference in.
+    /// An increase pays the creation fee like a top-up.
+    pub fn accept_amendment(
+        env: Env,
+        stream_id: u64,
+        acceptor: Address,
+        terms: AmendmentTerms,
+    ) -> Result<(), Error> {
+        acceptor.require_auth();
+        Self::require_admin_active(&env)?;
 
-        let key = (STREAM_COUNT, stream_id);
-        let mut stream: Stream = env
-            .storage()
-            .instance()
-            .get(&key)
-            .ok_or(Error::StreamNotFound)?;
+        let mut stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+        let pending = storage::load_amendment(&env, stream_id).ok_or(Error::NoPendingAmendment)?;
+        if pending.terms != terms {
+            return Err(Error::AmendmentMismatch);
+        }
 
-        if stream.receiver != caller {
+        let sender_accepts = pending.proposer == stream.receipt_owner && acceptor == stream.sender;
+        let receiver_accepts =
+            pending.proposer == stream.sender && acceptor == stream.receipt_owner;
+        if !sender_accepts && !receiver_accepts {
             return Err(Error::Unauthorized);
         }
+        Self::require_amendable(&stream)?;
 
-        if stream.cancelled {
-            return Err(Error::AlreadyCancelled);
-        }
-        if stream.is_paused {
-            return Err(Error::StreamPaused);
+        let now = env.ledger().timestamp();
+        let unlocked = Self::calculate_unlocked(&stream, now);
+        if terms.total_amount < unlocked || terms.end_time <= now {
+            return Err(Error::InvalidAmendment);
         }
 
-        let current_time = env.ledger().timestamp();
-        let unlocked = Self::calculate_unlocked(&stream, current_time);
-        let to_withdraw = unlocked - stream.withdrawn_amount;
-
-        if to_withdraw <= 0 {
-            return Err(Error::InsufficientBalance);
+        // Settle the change in total, plus the creation fee on any increase,
+        // against what the proposal escrowed. A sender's proposal escrowed
+        // the fee due when it was made.
+        let old_total = stream.total_amount;
+        let increase = (terms.total_amount - old_total).max(0);
+        let fee = if pending.escrowed > 0 {
+            pending.escrowed - increase
+        } else {
+            Self::creation_fee(&env, increase)
+        };
+        // A vault stream refunds a lower total out of its vault, yield included
+        let decrease = (old_total - terms.total_amount).max(0);
+        let vault_yield =
+            Self::redeem_from_vault(&env, stream_id, &mut stream, decrease)? - decrease;
+        let sender_owes = terms.total_amount - old_total + fee - pending.escrowed - vault_yield;
+        let token_client = token::Client::new(&env, &stream.token);
+        if sender_owes > 0 {
+            token_client.transfer(
+                &stream.sender,
+                &env.current_contract_address(),
+                &sender_owes,
+            );
+        } else if sender_owes < 0 {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &stream.sender,
+                &-sender_owes,
+            );
+        }
+        Self::pay_fee(&env, stream_id, &stream.token, fee);
+
+        // The new schedule was laid out from the proposal; stretch it onto
+        // what is left of that window
+        stream.carried_amount = unlocked;
+        stream.total_amount = terms.total_amount;
+        stream.start_time = pending.proposed_at;
+        stream.end_time = terms.end_time;
+        stream.curve_type = terms.curve_type;
+        Self::reschedule(&mut stream, now, terms.end_time);
+        stream.cliff_time = terms.cliff_time.clamp(now, stream.end_time);
+        stream.milestones = Vec::new(&env);
+        stream.initial_unlock_bps = 0;
+        stream.total_paused_duration = 0;
+
+        let new_receiver = terms.receiver;
+        let receiver_changed = new_receiver != stream.receipt_owner;
+        if receiver_changed {
+            index::transfer(
+                &env,
+                StreamIndex::Receiver,
+                &stream.receipt_owner,
+                &new_receiver,
+                stream_id,
+            );
+            stream.receiver = new_receiver.clone();
+            stream.receipt_owner = new_receiver.clone();
         }
 
-        stream.withdrawn_amount += to_withdraw;
-        env.storage().instance().set(&key, &stream);
+        storage::remove_amendment(&env, stream_id);
+        storage::save_stream(&env, stream_id, &stream);
+        if receiver_changed {
+            Self::mint_receipt(&env, stream_id, &new_receiver);
+        }
 
-        let token_client = token::Client::new(&env, &stream.token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &stream.receiver,
-            &to_withdraw,
+        env.events().publish(
+            (symbol_short!("amended"), stream_id),
+            StreamAmendedEvent {
+                stream_id,
+                accepted_by: acceptor,
+                old_total,
+                new_total: stream.total_amount,
+                new_end_time: stream.end_time,
+                carried_amount: unlocked,
+                timestamp: now,
+            },
         );
 
-        Ok(to_withdraw)
+        Ok(())
     }
 
-    pub fn cancel(env: Env, stream_id: u64, caller: Address) -> Result<(), Error> {
+    /// Withdraw or decline the pending amendment of a stream. Either party
+    /// can call this; any funds the sender escrowed are returned.
+    pub fn reject_amendment(env: Env, stream_id: u64, caller: Address) -> Result<(), Error> {
         caller.require_auth();
 
-        let key = (STREAM_COUNT, stream_id);
-        let mut stream: Stream = env
-            .storage()
-            .instance()
-            .get(&key)
-            .ok_or(Error::StreamNotFound)?;
-
-        if stream.sender != caller && stream.receiver != caller {
+        let stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+        if stream.sender != caller && stream.receipt_owner != caller {
             return Err(Error::Unauthorized);
         }
-        if stream.cancelled {
-            return Err(Error::AlreadyCancelled);
+        if storage::load_amendment(&env, stream_id).is_none() {
+            return Err(Error::NoPendingAmendment);
         }
+        Self::clear_amendment(&env, stream_id, &stream);
 
-        let current_time = env.ledger().timestamp();
-        let unlocked = Self::calculate_unlocked(&stream, current_time);
-        let to_receiver = unlocked - stream.withdrawn_amount;
-        let to_sender = stream.total_amount - unlocked;
+        Ok(())
+    }
 
-        stream.cancelled = true;
-        stream.withdrawn_amount = unlocked;
-        env.storage().instance().set(&key, &stream);
+    pub fn get_amendment(env: Env, stream_id: u64) -> Option<StreamAmendment> {
+        storage::load_amendment(&env, stream_id)
+    }
 
-        let token_client = token::Client::new(&env, &stream.token);
-        if to_receiver > 0 {
-            token_client.transfer(
-                &env.current_contract_address(),
-                &stream.receiver,
-                &to_receiver,
-            );
+    /// Amendments apply to running streams on a fixed schedule
+    fn require_amendable(stream: &Stream) -> Result<(), Error> {
+        if stream.cancelled {
+            return Err(Error::AlreadyCancelled);
         }
-        if to_sender > 0 {
-            token_client.transfer(&env.current_contract_address(), &stream.sender, &to_sender);
+        if stream.is_paused {
+            return Err(Error::StreamPaused);
+        }
+        if stream.is_frozen {
+            return Err(Error::StreamFrozen);
+        }
+        if stream.rate != StreamRate::Scheduled {
+            return Err(Error::InvalidAmendment);
         }
-
         Ok(())
     }
 
-    fn calculate_unlocked(stream: &Stream, current_time: u64) -> i128 {
-        if current_time <= stream.start_time {
-            return 0;
-        }
-
-        let mut effective_time = current_time;
-        if stream.is_paused {
-            effective_time = stream.paused_time;
+    /// Drop a stream's pending amendment, returning anything the sender
+    /// escrowed with it
+    fn clear_amendment(env: &Env, stream_id: u64, stream: &Stream) {
+        let Some(pending) = storage::load_amendment(env, stream_id) else {
+            return;
+        };
+        storage::remove_amendment(env, stream_id);
+        if pending.escrowed > 0 {
+            let token_client = token::Client::new(env, &stream.token);
+            token_client.transfer(
+                &env.current_contract_address(),
+                &stream.sender,
+                &pending.escrowed,
+            );
         }
+    }
 
-        let adjusted_end = stream.end_time + stream.total_paused_duration;
-        if effective_time >= adjusted_end {
-            return stream.total_amount;
-        }
+    // --- SHARED SENDER DEPOSITS ---
 
-        let elapsed = (effective_time - stream.start_time) as i128;
-        let paused = stream.total_paused_duration as i128;
-        let effective_elapsed = elapsed - paused;
+    /// Add funds to the sender's shared deposit for `token`, from which any
+    /// number of `create_deposit_stream` streams draw. Returns the new
+    /// balance. Topping up a deposit that ran out also pays its streams for
+    /// the time they went unfunded.
+    pub fn deposit(env: Env, sender: Address, token: Address, amount: i128) -> Result<i128, Error> {
+        sender.require_auth();
+        Self::require_creation_active(&env)?;
 
-        if effective_elapsed <= 0 {
-            return 0;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
         }
 
-        let duration = (stream.end_time - stream.start_time) as i128;
-
-        // Calculate base unlocked amount based on curve type
-        match stream.curve_type {
-            CurveType::Linear => (stream.total_amount * effective_elapsed) / duration,
-            CurveType::Exponential => {
-                // Use exponential curve with overflow protection
-                let adjusted_start = stream.start_time;
-                let adjusted_current = stream.start_time + effective_elapsed as u64;
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&sender, &env.current_contract_address(), &amount);
 
-                math::calculate_exponential_unlocked(
-                    stream.total_amount,
-                    adjusted_start,
-                    stream.end_time,
-                    adjusted_current,
-                )
-                .unwrap_or((stream.total_amount * effective_elapsed) / duration)
-            }
-        }
+        let mut deposit = storage::load_deposit(&env, &sender, &token);
+        Self::settle_deposit(&mut deposit, env.ledger().timestamp());
+        deposit.balance += amount;
+        storage::save_deposit(&env, &sender, &token, &deposit);
 
-        let duration = (stream.end_time - stream.start_time) as i128;
-        (total_usd * effective_elapsed) / duration
+        Ok(deposit.balance)
     }
 
-    // ========== RBAC Functions ==========
+    /// Take back part of the shared deposit that streams have not drawn yet.
+    /// Returns the remaining balance.
+    pub fn withdraw_excess(
+        env: Env,
+        sender: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        sender.require_auth();
+        Self::require_withdrawal_active(&env)?;
 
-    /// Grant a role to an address (Admin only)
-    pub fn grant_role(env: Env, admin: Address, target: Address, role: Role) {
-        admin.require_auth();
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
 
-        // Check if caller has Admin role
-        if !Self::has_role(&env, &admin, Role::Admin) {
-            panic!("{}", Error::Unauthorized as u32);
+        let mut deposit = storage::load_deposit(&env, &sender, &token);
+        Self::settle_deposit(&mut deposit, env.ledger().timestamp());
+        if amount > deposit.balance {
+            return Err(Error::InsufficientBalance);
         }
+        deposit.balance -= amount;
+        storage::save_deposit(&env, &sender, &token, &deposit);
 
-        // Grant the role
-        env.storage()
-            .instance()
-            .set(&DataKey::Role(target.clone(), role.clone()), &true);
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &sender, &amount);
 
-        // Emit event
-        env.events().publish((symbol_short!("grant"), target), role);
+        Ok(deposit.balance)
     }
 
-    /// Revoke a role from an address (Admin only)
-    pub fn revoke_role(env: Env, admin: Address, target: Address, role: Role) {
-        admin.require_auth();
+    /// Create an open-ended stream paid from the sender's shared deposit for
+    /// `token` instead of its own escrow. It accrues `rate_per_second` from
+    /// now until cancelled or until the deposit runs out, which must be able
+    /// to fund at least its first second.
+    pub fn create_deposit_stream(
+        env: Env,
+        sender: Address,
+        receiver: Address,
+        token: Address,
+        rate_per_second: i128,
+    ) -> Result<u64, Error> {
+        sender.require_auth();
+        Self::require_creation_active(&env)?;
 
-        // Check if caller has Admin role
-        if !Self::has_role(&env, &admin, Role::Admin) {
-            return; // Error::Unauthorized;
+        if rate_per_second <= 0 {
+            return Err(Error::InvalidAmount);
         }
 
-        // Revoke the role
-        env.storage()
-            .instance()
-            .remove(&DataKey::Role(target.clone(), role.clone()));
-
-        // Emit event
-        env.events()
-            .publish((symbol_short!("revoke"), target), role);
-    }
+        let now = env.ledger().timestamp();
+        let mut deposit = storage::load_deposit(&env, &sender, &token);
+        if deposit.streams.len() >= MAX_DEPOSIT_STREAMS {
+            return Err(Error::TooManyDepositStreams);
+        }
+        // The deposit must fund at least the first second of every stream it
+        // pays, this one included. One that already ran out must be refilled
+        // or liquidated first.
+        Self::settle_deposit(&mut deposit, now);
+        let outgoing_rate = deposit
+            .outgoing_rate
+            .checked_add(rate_per_second)
+            .ok_or(Error::InvalidAmount)?;
+        if deposit.balance < outgoing_rate {
+            return Err(Error::InsufficientBalance);
+        }
 
-    /// Check if an address has a specific role
-    pub fn check_role(env: Env, address: Address, role: Role) -> bool {
-        Self::has_role(&env, &address, role)
-    }
+        let stream_id = Self::store_new_stream(
+            &env,
+            sender.clone(),
+            receiver,
+            token.clone(),
+            0,
+            now,
+            u64::MAX,
+            StreamOptions::new(&env),
+            StreamRate::FromDeposit(RateSchedule {
+                rate_per_second,
+                checkpoint_time: now,
+                accrued: 0,
+                insolvent: false,
+            }),
+        )?;
+        deposit.outgoing_rate = outgoing_rate;
+        deposit.streams.push_back(stream_id);
+        storage::save_deposit(&env, &sender, &token, &deposit);
 
-    /// Internal helper to check if an address has a role
-    fn has_role(env: &Env, address: &Address, role: Role) -> bool {
-        env.storage()
-            .instance()
-            .get(&DataKey::Role(address.clone(), role))
-            .unwrap_or(false)
+        Ok(stream_id)
     }
 
-    // ========== Contract Upgrade Functions ==========
+    /// Stop every stream funded from a sender's shared deposit once it has
+    /// run out, paying receivers what it funded. Anyone can call this.
+    /// Returns the number of streams stopped.
+    pub fn liquidate_deposit(env: Env, sender: Address, token: Address) -> Result<u32, Error> {
+        Self::require_withdrawal_active(&env)?;
 
-    /// Upgrade the contract to a new WASM hash
-    /// Only addresses with Admin role can perform this operation
-    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: soroban_sdk::BytesN<32>) {
-        admin.require_auth();
+        let now = env.ledger().timestamp();
+        let mut deposit = storage::load_deposit(&env, &sender, &token);
+        let funded_until = math::calculate_funded_until(
+            deposit.balance,
+            deposit.outgoing_rate,
+            deposit.checkpoint_time,
+        );
+        if deposit.outgoing_rate == 0 || now < funded_until {
+            return Err(Error::DepositNotDepleted);
+        }
 
-        // Check if caller has Admin role
-        if !Self::has_role(&env, &admin, Role::Admin) {
-            return; // Error::Unauthorized;
+        Self::settle_deposit(&mut deposit, now);
+        let stream_ids = deposit.streams.clone();
+        for stream_id in stream_ids.iter() {
+            let mut stream = storage::load_stream(&env, stream_id).ok_or(Error::StreamNotFound)?;
+            let owed = Self::stop_deposit_stream(stream_id, &mut stream, &mut deposit);
+            storage::save_stream(&env, stream_id, &stream);
+            Self::pay_receiver(&env, stream_id, &stream, owed);
         }
+        storage::save_deposit(&env, &sender, &token, &deposit);
 
-        // Update the contract WASM
-        env.deployer()
-            .update_current_contract_wasm(new_wasm_hash.clone());
+        env.events().publish(
+            (symbol_short!("liquidate"), sender.clone()),
+            DepositLiquidatedEvent {
+                sender,
+                token,
+                streams_stopped: stream_ids.len(),
+                depleted_at: funded_until,
+                timestamp: now,
+            },
+        );
 
-        // Emit upgrade event with new WASM hash
-        env.events()
-            .publish((symbol_short!("upgrade"), admin), new_wasm_hash);
+        Ok(stream_ids.len())
     }
 
-    /// Get the current admin address (for backward compatibility)
-    pub fn get_admin(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set")
+    /// Balance, total outgoing rate and depletion time of a sender's shared
+    /// deposit for `token`
+    pub fn get_deposit_solvency(env: Env, sender: Address, token: Address) -> DepositSolvency {
+        let now = env.ledger().timestamp();
+        let mut deposit = storage::load_deposit(&env, &sender, &token);
+        Self::settle_deposit(&mut deposit, now);
+        let funded_until = math::calculate_funded_until(
+            deposit.balance,
+            deposit.outgoing_rate,
+            deposit.checkpoint_time,
+        );
+        let streaming = deposit.outgoing_rate > 0;
+
+        DepositSolvency {
+            balance: deposit.balance,
+            outgoing_rate: deposit.outgoing_rate,
+            depletion_time: streaming.then_some(funded_until),
+            is_depleted: streaming && funded_until <= now,
+        }
     }
 
     // --- CONTRIBUTOR PULL-REQUEST PAYMENTS ---
@@ -884,6 +3790,9 @@ impl StellarStreamContract {
         metadata: Option<soroban_sdk::BytesN<32>>,
     ) -> u64 {
         receiver.require_auth();
+        if let Err(err) = Self::require_creation_active(&env) {
+            panic_with_error!(&env, err);
+        }
         let count: u64 = env
             .storage()
             .instance()
@@ -923,6 +3832,7 @@ impl StellarStreamContract {
 
     pub fn execute_request(env: Env, admin: Address, request_id: u64) -> Result<u64, Error> {
         admin.require_auth();
+        Self::require_creation_active(&env)?;
         if !Self::has_role(&env, &admin, Role::Admin) {
             return Err(Error::Unauthorized);
         }
@@ -947,6 +3857,7 @@ impl StellarStreamContract {
             request.start_time,
             request.start_time + request.duration,
             CurveType::Linear,
+            false,
         )?;
         env.events().publish(
             (
@@ -972,10 +3883,11 @@ impl StellarStreamContract {
 
 // Contract metadata for explorer display (Stellar.Expert, etc.)
 soroban_sdk::contractmeta!(
-    desc = "StellarStream: Token streaming with multi-sig proposals, dynamic vesting curves (linear/exponential), yield optimization, and OFAC compliance. Create, manage, and withdraw from streams with flexible approval workflows.",
-    version = "0.1.0",
-    name = "StellarStream"
+    key = "desc",
+    val = "StellarStream: Token streaming with multi-sig proposals, dynamic vesting curves (linear/exponential), yield optimization, and OFAC compliance. Create, manage, and withdraw from streams with flexible approval workflows."
 );
+soroban_sdk::contractmeta!(key = "version", val = "0.1.0");
+soroban_sdk::contractmeta!(key = "name", val = "StellarStream");
 
 #[cfg(test)]
 mod test {
@@ -1048,13 +3960,13 @@ mod test {
 
         let proposal = client.get_proposal(&proposal_id);
         assert_eq!(proposal.approvers.len(), 1);
-        assert!(!proposal.executed);
 
         client.approve_proposal(&proposal_id, &approver2);
 
-        let proposal = client.get_proposal(&proposal_id);
-        assert_eq!(proposal.approvers.len(), 2);
-        assert!(proposal.executed);
+        // Executed proposals are removed; the stream remains
+        let result = client.try_get_proposal(&proposal_id);
+        assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+        assert_eq!(client.get_stream(&0).total_amount, 1000);
     }
 
     #[test]
@@ -1177,6 +4089,7 @@ mod test {
             &100,
             &200,
             &CurveType::Linear,
+            &false,
         );
 
         assert_eq!(stream_id, 0);
@@ -1217,6 +4130,7 @@ mod test {
             &100,
             &200,
             &CurveType::Linear,
+            &false,
         );
 
         client.transfer_receipt(&stream_id, &receiver, &new_owner);
@@ -1254,6 +4168,7 @@ mod test {
             &100,
             &200,
             &CurveType::Linear,
+            &false,
         );
 
         client.transfer_receipt(&stream_id, &receiver, &new_owner);
@@ -1290,6 +4205,7 @@ mod test {
             &100,
             &200,
             &CurveType::Linear,
+            &false,
         );
 
         let metadata = client.get_receipt_metadata(&stream_id);
@@ -1326,16 +4242,16 @@ mod test {
 
         client.approve_proposal(&proposal_id, &approver1);
         let proposal = client.get_proposal(&proposal_id);
-        assert!(!proposal.executed);
+        assert_eq!(proposal.approvers.len(), 1);
 
         client.approve_proposal(&proposal_id, &approver2);
         let proposal = client.get_proposal(&proposal_id);
-        assert!(!proposal.executed);
+        assert_eq!(proposal.approvers.len(), 2);
 
         client.approve_proposal(&proposal_id, &approver3);
-        let proposal = client.get_proposal(&proposal_id);
-        assert!(proposal.executed);
-        assert_eq!(proposal.approvers.len(), 3);
+        let result = client.try_get_proposal(&proposal_id);
+        assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+        assert_eq!(client.get_stream(&0).total_amount, 50000);
     }
 
     #[test]
@@ -1364,7 +4280,7 @@ mod test {
         let approver2 = Address::generate(&env);
         let result = client.try_approve_proposal(&proposal_id, &approver2);
 
-        assert_eq!(result, Err(Ok(Error::ProposalAlreadyExecuted)));
+        assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
     }
 
     #[test]
@@ -1392,6 +4308,7 @@ mod test {
             &100,
             &300,
             &CurveType::Linear,
+            &false,
         );
 
         env.ledger().with_mut(|li| li.timestamp = 150);
@@ -1434,6 +4351,7 @@ mod test {
             &100,
             &300,
             &CurveType::Linear,
+            &false,
         );
 
         client.pause_stream(&stream_id, &sender);
@@ -1469,6 +4387,7 @@ mod test {
             &100,
             &300,
             &CurveType::Linear,
+            &false,
         );
 
         env.ledger().with_mut(|li| li.timestamp = 150);
@@ -1506,6 +4425,8 @@ mod test {
         let token_admin_client = StellarAssetClient::new(&env, &token_id);
         token_admin_client.mint(&sender, &10000);
 
+        // Cumulative: 25% more unlocks at the end of each quarter, nothing
+        // streams in between
         let mut milestones = Vec::new(&env);
         milestones.push_back(Milestone {
             timestamp: 90,
@@ -1533,19 +4454,29 @@ mod test {
             &360,
             &milestones,
             &CurveType::Linear,
+            &false,
+            &None,
         );
 
-        env.ledger().with_mut(|li| li.timestamp = 45);
-        let metadata = client.get_receipt_metadata(&stream_id);
-        assert!(metadata.unlocked_balance <= 250);
+        // Before the first quarter closes nothing is claimable
+        env.ledger().with_mut(|li| li.timestamp = 89);
+        let result = client.try_withdraw(&stream_id, &receiver);
+        assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
 
-        env.ledger().with_mut(|li| li.timestamp = 100);
-        let metadata = client.get_receipt_metadata(&stream_id);
-        assert_eq!(metadata.unlocked_balance, 250);
+        env.ledger().with_mut(|li| li.timestamp = 90);
+        assert_eq!(client.withdraw(&stream_id, &receiver), 250);
+
+        // Mid-quarter the step does not move
+        env.ledger().with_mut(|li| li.timestamp = 179);
+        let result = client.try_withdraw(&stream_id, &receiver);
+        assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
 
         env.ledger().with_mut(|li| li.timestamp = 200);
-        let metadata = client.get_receipt_metadata(&stream_id);
-        assert_eq!(metadata.unlocked_balance, 500);
+        assert_eq!(client.withdraw(&stream_id, &receiver), 250);
+
+        env.ledger().with_mut(|li| li.timestamp = 360);
+        assert_eq!(client.withdraw(&stream_id, &receiver), 500);
+        assert_eq!(client.get_stream(&stream_id).withdrawn_amount, 1000);
     }
 
     #[test]
@@ -1565,6 +4496,7 @@ mod test {
         let token_admin_client = StellarAssetClient::new(&env, &token_id);
         token_admin_client.mint(&sender, &10000);
 
+        // 50% step at t=100, the remaining 50% streams linearly over [0, 200]
         let mut milestones = Vec::new(&env);
         milestones.push_back(Milestone {
             timestamp: 100,
@@ -1580,19 +4512,21 @@ mod test {
             &200,
             &milestones,
             &CurveType::Linear,
+            &false,
+            &None,
         );
 
+        // Linear part only: 500 * 50 / 200
         env.ledger().with_mut(|li| li.timestamp = 50);
-        let metadata = client.get_receipt_metadata(&stream_id);
-        assert!(metadata.unlocked_balance <= 250);
+        assert_eq!(client.withdraw(&stream_id, &receiver), 125);
 
+        // Step floor of 500 plus 500 * 150 / 200 streamed
         env.ledger().with_mut(|li| li.timestamp = 150);
-        let metadata = client.get_receipt_metadata(&stream_id);
-        assert_eq!(metadata.unlocked_balance, 500);
+        assert_eq!(client.withdraw(&stream_id, &receiver), 875 - 125);
 
         env.ledger().with_mut(|li| li.timestamp = 200);
-        let metadata = client.get_receipt_metadata(&stream_id);
-        assert_eq!(metadata.unlocked_balance, 1000);
+        assert_eq!(client.withdraw(&stream_id, &receiver), 125);
+        assert_eq!(client.get_stream(&stream_id).withdrawn_amount, 1000);
     }
 
     // ============================================================================
@@ -1625,6 +4559,7 @@ mod test {
             &100,
             &200,
             &CurveType::Linear,
+            &false,
         );
 
         assert_eq!(stream_id, 0);
@@ -1656,6 +4591,7 @@ mod test {
             &100,
             &200,
             &CurveType::Linear,
+            &false,
         );
 
         // Withdraw - should emit claim event
@@ -1689,6 +4625,7 @@ mod test {
             &100,
             &200,
             &CurveType::Linear,
+            &false,
         );
 
         // Cancel - should emit cancel event
@@ -1721,6 +4658,7 @@ mod test {
             &100,
             &200,
             &CurveType::Linear,
+            &false,
         );
 
         // Transfer receipt - should emit transfer event
@@ -1753,6 +4691,7 @@ mod test {
             &100,
             &300,
             &CurveType::Linear,
+            &false,
         );
 
         // Pause stream - should emit pause event
@@ -1785,6 +4724,7 @@ mod test {
             &100,
             &300,
             &CurveType::Linear,
+            &false,
         );
 
         client.pause_stream(&stream_id, &sender);
@@ -1853,6 +4793,7 @@ mod test {
             &0,
             &100,
             &CurveType::Exponential,
+            &false,
         );
 
         // At 50% time: should have ~25% unlocked (0.5^2 = 0.25)
@@ -1943,7 +4884,7 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #20)")]
+    #[should_panic(expected = "Error(Contract, #22)")]
     fn test_cannot_create_stream_to_restricted_address() {
         let env = Env::default();
         env.mock_all_auths();
@@ -1960,7 +4901,9 @@ mod test {
         set_admin_role(&env, &contract_id, &admin);
 
         // Create token
-        let token_id = env.register_stellar_asset_contract(token_admin.clone());
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin.clone())
+            .address();
 
         // Mint tokens to sender
         let token_client = token::StellarAssetClient::new(&env, &token_id);
@@ -1978,11 +4921,12 @@ mod test {
             &100,
             &200,
             &CurveType::Linear,
+            &false,
         );
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #20)")]
+    #[should_panic(expected = "Error(Contract, #22)")]
     fn test_cannot_create_proposal_to_restricted_address() {
         let env = Env::default();
         env.mock_all_auths();
@@ -1999,7 +4943,9 @@ mod test {
         set_admin_role(&env, &contract_id, &admin);
 
         // Create token
-        let token_id = env.register_stellar_asset_contract(token_admin.clone());
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin.clone())
+            .address();
 
         // Mint tokens to sender
         let token_client = token::StellarAssetClient::new(&env, &token_id);
@@ -2022,7 +4968,7 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #20)")]
+    #[should_panic(expected = "Error(Contract, #22)")]
     fn test_cannot_transfer_receipt_to_restricted_address() {
         let env = Env::default();
         env.mock_all_auths();
@@ -2040,7 +4986,9 @@ mod test {
         set_admin_role(&env, &contract_id, &admin);
 
         // Create token
-        let token_id = env.register_stellar_asset_contract(token_admin.clone());
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin.clone())
+            .address();
 
         // Mint tokens to sender
         let token_client = token::StellarAssetClient::new(&env, &token_id);
@@ -2055,6 +5003,7 @@ mod test {
             &100,
             &200,
             &CurveType::Linear,
+            &false,
         );
 
         // Admin restricts an address
@@ -2137,7 +5086,9 @@ mod test {
         set_admin_role(&env, &contract_id, &admin);
 
         // Create token
-        let token_id = env.register_stellar_asset_contract(token_admin.clone());
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin.clone())
+            .address();
 
         // Mint tokens to sender
         let token_client = token::StellarAssetClient::new(&env, &token_id);
@@ -2158,9 +5109,9 @@ mod test {
             &100,
             &200,
             &CurveType::Linear,
+            &false,
         );
 
-        // Verify stream was created (stream_id >= 0)
-        assert!(stream_id >= 0);
+        assert_eq!(client.get_stream(&stream_id).total_amount, 1000);
     }
 }
@@ -1,5 +1,140 @@
 #![allow(unexpected_cfgs)]
 
+use soroban_sdk::Vec;
+
+use crate::types::{CurvePoint, POWER_EXPONENT_SCALE};
+
+/// 1.0 in Q64.64 fixed point
+const Q64: u128 = 1 << 64;
+
+/// 2^(2^-i) for i = 1..=48 in Q64.64, rounded down
+const EXP2_FRACTION_BITS: [u128; 48] = [
+    0x16a09e667f3bcc908,
+    0x1306fe0a31b7152de,
+    0x1172b83c7d517adcd,
+    0x10b5586cf9890f629,
+    0x1059b0d31585743ae,
+    0x102c9a3e778060ee6,
+    0x10163da9fb33356d8,
+    0x100b1afa5abcbed61,
+    0x10058c86da1c09ea1,
+    0x1002c605e2e8cec50,
+    0x100162f3904051fa1,
+    0x1000b175effdc76ba,
+    0x100058ba01fb9f96d,
+    0x10002c5cc37da9491,
+    0x1000162e525ee0547,
+    0x10000b17255775c04,
+    0x1000058b91b5bc9ae,
+    0x100002c5c89d5ec6c,
+    0x10000162e43f4f831,
+    0x100000b1721bcfc99,
+    0x10000058b90cf1e6d,
+    0x1000002c5c863b73f,
+    0x100000162e430e5a1,
+    0x1000000b172183551,
+    0x100000058b90c0b48,
+    0x10000002c5c8601cc,
+    0x1000000162e42fff0,
+    0x10000000b17217fba,
+    0x1000000058b90bfcd,
+    0x100000002c5c85fe3,
+    0x10000000162e42ff0,
+    0x100000000b17217f8,
+    0x10000000058b90bfb,
+    0x1000000002c5c85fd,
+    0x100000000162e42fe,
+    0x1000000000b17217f,
+    0x100000000058b90bf,
+    0x10000000002c5c85f,
+    0x1000000000162e42f,
+    0x10000000000b17217,
+    0x1000000000058b90b,
+    0x100000000002c5c85,
+    0x10000000000162e42,
+    0x100000000000b1721,
+    0x10000000000058b90,
+    0x1000000000002c5c8,
+    0x100000000000162e4,
+    0x1000000000000b172,
+];
+
+/// Multiply two u128 values into a 256-bit (high, low) pair
+fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    let mask = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & mask);
+    let (b_hi, b_lo) = (b >> 64, b & mask);
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    // Each term is below 2^64, so the middle column cannot overflow
+    let mid = (lo_lo >> 64) + (hi_lo & mask) + (lo_hi & mask);
+    let lo = (lo_lo & mask) | (mid << 64);
+    let hi = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+/// Divide a 256-bit (high, low) value by `denominator`, returning quotient
+/// and remainder. Fails when the quotient does not fit in 128 bits.
+fn div_rem_wide(hi: u128, lo: u128, denominator: u128) -> Result<(u128, u128), ()> {
+    if denominator == 0 || hi >= denominator {
+        return Err(());
+    }
+    if hi == 0 {
+        return Ok((lo / denominator, lo % denominator));
+    }
+
+    // Shift-subtract long division; `rem` stays below `denominator`
+    let mut rem = hi;
+    let mut quotient: u128 = 0;
+    for bit in (0..128).rev() {
+        let carry = rem >> 127;
+        rem = (rem << 1) | ((lo >> bit) & 1);
+        quotient <<= 1;
+        if carry == 1 || rem >= denominator {
+            rem = rem.wrapping_sub(denominator);
+            quotient |= 1;
+        }
+    }
+    Ok((quotient, rem))
+}
+
+/// Compute floor(a * b / denominator) through a 256-bit intermediate, so the
+/// product itself can never overflow
+/// Fails only on a zero denominator or a quotient wider than 128 bits
+pub fn mul_div_floor_u128(a: u128, b: u128, denominator: u128) -> Result<u128, ()> {
+    let (hi, lo) = widening_mul(a, b);
+    div_rem_wide(hi, lo, denominator).map(|(quotient, _)| quotient)
+}
+
+/// Compute floor(a * b / denominator) at full precision
+/// Always rounds DOWN (towards negative infinity) to favor contract solvency
+/// Fails only on a zero denominator or a result outside i128
+pub fn mul_div_floor(a: i128, b: i128, denominator: i128) -> Result<i128, ()> {
+    let negative = (a < 0) ^ (b < 0) ^ (denominator < 0);
+    let (hi, lo) = widening_mul(a.unsigned_abs(), b.unsigned_abs());
+    let (quotient, rem) = div_rem_wide(hi, lo, denominator.unsigned_abs())?;
+
+    if !negative || (quotient == 0 && rem == 0) {
+        return i128::try_from(quotient).map_err(|_| ());
+    }
+    let magnitude = quotient + (rem != 0) as u128;
+    if magnitude > i128::MIN.unsigned_abs() {
+        return Err(());
+    }
+    Ok((magnitude as i128).wrapping_neg())
+}
+
+/// Calculate `amount * part / whole` for `0 <= part <= whole`, rounded DOWN
+/// The result never exceeds `amount`, so this cannot overflow; an empty
+/// `whole` yields nothing
+pub fn portion_floor(amount: i128, part: i128, whole: i128) -> i128 {
+    mul_div_floor(amount, part, whole).unwrap_or(0)
+}
+
 /// Calculate unlocked amount with precision-safe rounding
 /// Always rounds DOWN to favor contract solvency
 #[allow(dead_code)]
@@ -20,14 +155,13 @@ pub fn calculate_unlocked_amount(
     let elapsed_time = (current_time - start_time) as i128;
     let total_duration = (end_time - start_time) as i128;
 
-    // Integer division automatically rounds down (floor division)
-    // This ensures we never unlock more than we should
-    (total_amount * elapsed_time) / total_duration
+    // Floor division ensures we never unlock more than we should
+    portion_floor(total_amount, elapsed_time, total_duration)
 }
 
 /// Calculate unlocked amount using exponential curve (quadratic growth)
 /// Accelerates payout as stream approaches end_time
-/// Uses checked math to prevent overflow
+/// Exact for any i128 supply; fails only for a negative total
 pub fn calculate_exponential_unlocked(
     total_amount: i128,
     start_time: u64,
@@ -42,16 +176,222 @@ pub fn calculate_exponential_unlocked(
         return Ok(total_amount);
     }
 
-    let elapsed = (current_time - start_time) as i128;
-    let duration = (end_time - start_time) as i128;
+    if total_amount < 0 {
+        return Err(());
+    }
+
+    let elapsed = (current_time - start_time) as u128;
+    let duration = (end_time - start_time) as u128;
 
     // Quadratic formula: unlocked = total * (elapsed^2 / duration^2)
-    // Rearranged to minimize overflow: (total * elapsed * elapsed) / (duration * duration)
-    let elapsed_squared = elapsed.checked_mul(elapsed).ok_or(())?;
-    let duration_squared = duration.checked_mul(duration).ok_or(())?;
-    let numerator = total_amount.checked_mul(elapsed_squared).ok_or(())?;
+    // Squares of u64 durations always fit in u128
+    let unlocked =
+        mul_div_floor_u128(total_amount as u128, elapsed * elapsed, duration * duration)?;
+
+    // Bounded by total_amount since elapsed < duration
+    Ok(unlocked as i128)
+}
+
+/// Multiply two Q64.64 values, rounding down
+fn mul_q64(a: u128, b: u128) -> u128 {
+    let (hi, lo) = widening_mul(a, b);
+    (hi << 64) | (lo >> 64)
+}
+
+/// log2 of a Q64.64 value in (0, 1), as a negative Q64.64
+/// Every step truncates, so the result is monotonic in `x`
+fn log2_q64(x: u128) -> i128 {
+    let msb = 127 - x.leading_zeros() as i128;
+    let mut result = (msb - 64) << 64;
 
-    Ok(numerator / duration_squared)
+    // Mantissa in [1, 2) with 63 fractional bits, so its square fits in u128
+    let mut y = if msb >= 63 {
+        x >> (msb - 63)
+    } else {
+        x << (63 - msb)
+    };
+    for bit in (0..64).rev() {
+        y = (y * y) >> 63;
+        if y >= 1 << 64 {
+            y >>= 1;
+            result += 1 << bit;
+        }
+    }
+    result
+}
+
+/// 2^e for a Q64.64 exponent `e <= 0`, as a Q64.64 mantissa in [1, 2) and
+/// the number of bits to shift it right by
+/// Only the top 48 fractional bits of `e` are used, which keeps every
+/// truncation far smaller than the gap between neighbouring results, so the
+/// result is monotonic in `e`
+fn exp2_q64(e: i128) -> (u128, u32) {
+    // e = fraction - whole, with 0 <= fraction < 1
+    let whole = (Q64 as i128 - 1 - e) >> 64;
+    let fraction = (e + (whole << 64)) as u128;
+
+    let mut mantissa = Q64;
+    for (i, factor) in EXP2_FRACTION_BITS.iter().enumerate() {
+        if (fraction >> (63 - i)) & 1 == 1 {
+            mantissa = mul_q64(mantissa, *factor);
+        }
+    }
+    (mantissa, whole.min(u32::MAX as i128) as u32)
+}
+
+/// Shift a 256-bit (high, low) value right by `shift` bits, assuming the
+/// result fits in 128 bits
+fn shr_wide(hi: u128, lo: u128, shift: u32) -> u128 {
+    match shift {
+        0 => lo,
+        1..=127 => (hi << (128 - shift)) | (lo >> shift),
+        128..=255 => hi >> (shift - 128),
+        _ => 0,
+    }
+}
+
+/// Calculate unlocked amount along a power curve: total * progress^exponent
+/// `exponent` is fixed-point, scaled by `POWER_EXPONENT_SCALE`
+/// Deterministic integer math via log2/exp2; rounds DOWN, is monotonic over
+/// time, never exceeds `total_amount` and returns it exactly at `end_time`
+pub fn calculate_power_unlocked(
+    total_amount: i128,
+    start_time: u64,
+    end_time: u64,
+    current_time: u64,
+    exponent: u32,
+) -> Result<i128, ()> {
+    if current_time <= start_time {
+        return Ok(0);
+    }
+
+    if current_time >= end_time {
+        return Ok(total_amount);
+    }
+
+    if total_amount < 0 || exponent == 0 {
+        return Err(());
+    }
+
+    let elapsed = (current_time - start_time) as u128;
+    let duration = (end_time - start_time) as u128;
+    let progress = mul_div_floor_u128(elapsed, Q64, duration)?;
+    if progress == 0 {
+        return Ok(0);
+    }
+
+    // progress^exponent = 2^(exponent * log2(progress)), rounding the
+    // (negative) exponent down so the result errs below the exact curve
+    let scaled_log = mul_div_floor(
+        log2_q64(progress),
+        exponent as i128,
+        POWER_EXPONENT_SCALE as i128,
+    )?;
+    let (mantissa, shift) = exp2_q64(scaled_log);
+
+    // Apply the power of two to the full product so small factors keep
+    // their precision
+    let (hi, lo) = widening_mul(total_amount as u128, mantissa);
+    let unlocked = shr_wide(hi, lo, shift.saturating_add(64));
+    Ok((unlocked as i128).min(total_amount))
+}
+
+/// Calculate unlocked amount along a custom piecewise-linear curve
+/// `elapsed` is measured from the stream start; the curve starts at (0, 0)
+/// Interpolates with a single division so the result always rounds DOWN,
+/// and returns exactly `total_amount` once the final 10_000 bps point is reached
+pub fn calculate_custom_unlocked(
+    total_amount: i128,
+    points: &Vec<CurvePoint>,
+    elapsed: u64,
+) -> Result<i128, ()> {
+    let mut prev_offset: u64 = 0;
+    let mut prev_bps: u32 = 0;
+
+    for point in points.iter() {
+        if elapsed < point.time_offset {
+            let span = (point.time_offset - prev_offset) as i128;
+            let into = (elapsed - prev_offset) as i128;
+            let rise = (point.unlocked_bps - prev_bps) as i128;
+
+            // bps reached so far, scaled by the segment length
+            let scaled_bps = (prev_bps as i128)
+                .checked_mul(span)
+                .ok_or(())?
+                .checked_add(rise.checked_mul(into).ok_or(())?)
+                .ok_or(())?;
+            let denominator = span.checked_mul(10_000).ok_or(())?;
+            return mul_div_floor(total_amount, scaled_bps, denominator);
+        }
+        prev_offset = point.time_offset;
+        prev_bps = point.unlocked_bps;
+    }
+
+    if prev_bps >= 10_000 {
+        return Ok(total_amount);
+    }
+    Ok(portion_floor(total_amount, prev_bps as i128, 10_000))
+}
+
+/// Calculate unlocked amount for a periodic (tranche) schedule
+/// Each completed period releases `total / tranches`, rounded DOWN; the final
+/// tranche releases whatever is left so the stream still ends at `total_amount`
+pub fn calculate_periodic_unlocked(
+    total_amount: i128,
+    period_secs: u64,
+    tranches: u32,
+    elapsed: u64,
+) -> Result<i128, ()> {
+    if period_secs == 0 || tranches == 0 {
+        return Err(());
+    }
+
+    let completed = elapsed / period_secs;
+    if completed >= tranches as u64 {
+        return Ok(total_amount);
+    }
+
+    Ok(portion_floor(
+        total_amount,
+        completed as i128,
+        tranches as i128,
+    ))
+}
+
+/// Calculate how much an open-ended rate stream has streamed
+/// `accrued` was locked in at `checkpoint`; from there `rate` accrues every
+/// second. Not capped by the deposit: anything above it is debt owed to the
+/// receiver.
+pub fn calculate_rate_streamed(accrued: i128, rate: i128, checkpoint: u64, now: u64) -> i128 {
+    let elapsed = now.saturating_sub(checkpoint) as i128;
+    rate.saturating_mul(elapsed).saturating_add(accrued)
+}
+
+/// Calculate when an open-ended rate stream runs out of balance
+/// Rounds UP to the first second at which everything has accrued
+pub fn calculate_depletion_time(
+    total_amount: i128,
+    accrued: i128,
+    rate: i128,
+    checkpoint: u64,
+) -> u64 {
+    let remaining = total_amount - accrued;
+    if remaining <= 0 || rate <= 0 {
+        return checkpoint;
+    }
+
+    let seconds = remaining / rate + (remaining % rate != 0) as i128;
+    checkpoint.saturating_add(u64::try_from(seconds).unwrap_or(u64::MAX))
+}
+
+/// Calculate until when a balance can fund `rate` per second from `checkpoint`
+/// Rounds DOWN: the leftover dust cannot fund another full second
+pub fn calculate_funded_until(balance: i128, rate: i128, checkpoint: u64) -> u64 {
+    if rate <= 0 {
+        return u64::MAX;
+    }
+    let seconds = balance.max(0) / rate;
+    checkpoint.saturating_add(u64::try_from(seconds).unwrap_or(u64::MAX))
 }
 
 /// Calculate withdrawable amount
@@ -81,9 +421,47 @@ pub fn calculate_unlocked(total_amount: i128, start: u64, cliff: u64, end: u64,
     let elapsed = (now - start) as i128;
     let total_duration = (end - start) as i128;
 
-    // Integer division rounds down (floor), favoring contract solvency
-    // This prevents over-withdrawal due to rounding errors
-    (total_amount * elapsed) / total_duration
+    // Floor division favors contract solvency and prevents over-withdrawal
+    portion_floor(total_amount, elapsed, total_duration)
+}
+
+/// Calculate the share released at start (TGE unlock)
+/// Rounds DOWN so any dust stays with the vesting remainder
+pub fn calculate_initial_unlock(total_amount: i128, initial_unlock_bps: u32) -> i128 {
+    portion_floor(total_amount, initial_unlock_bps as i128, 10_000)
+}
+
+/// Calculate unlocked amount with an initial unlock and cliff support
+/// `carried_amount` (earlier tranches) and the initial unlock are available
+/// from `start`; the cliff only gates the remainder. `vest` returns how much
+/// of that remainder has vested by `now` and is clamped to it, so the result
+/// stays within `[0, total_amount]` whatever the curve.
+pub fn calculate_unlocked_with_initial(
+    total_amount: i128,
+    carried_amount: i128,
+    initial_unlock_bps: u32,
+    start: u64,
+    cliff: u64,
+    end: u64,
+    now: u64,
+    vest: impl FnOnce(i128) -> i128,
+) -> i128 {
+    if now < start {
+        return 0;
+    }
+    // At or after end: return exact total to prevent dust
+    if now >= end {
+        return total_amount;
+    }
+
+    let initial = carried_amount
+        + calculate_initial_unlock(total_amount - carried_amount, initial_unlock_bps);
+    if now == start || now < cliff {
+        return initial;
+    }
+
+    let remainder = total_amount - initial;
+    initial + vest(remainder).clamp(0, remainder)
 }
 
 /// Calculate withdrawable amount with precision protection
@@ -117,7 +495,7 @@ pub fn calculate_fee(amount: i128, fee_bps: u32) -> i128 {
         return 0;
     }
     // fee_bps uses 10_000 as denominator (i.e., 10000 bps = 100%)
-    (amount * (fee_bps as i128)) / 10_000
+    portion_floor(amount, fee_bps as i128, 10_000)
 }
 
 #[cfg(test)]
@@ -150,6 +528,43 @@ mod test {
         assert_eq!(calculate_unlocked(total, start, cliff, end, 1000), 1000);
     }
 
+    #[test]
+    fn test_initial_unlock() {
+        let total = 1000_i128;
+        let start = 100;
+        let cliff = 150;
+        let end = 200;
+        let unlocked = |bps, now| {
+            calculate_unlocked_with_initial(total, 0, bps, start, cliff, end, now, |remainder| {
+                calculate_unlocked(remainder, start, cliff, end, now)
+            })
+        };
+
+        // 25.55% up front, rounded down; the remainder of 745 vests after the cliff
+        assert_eq!(calculate_initial_unlock(total, 2_555), 255);
+        assert_eq!(unlocked(2_555, 99), 0);
+        assert_eq!(unlocked(2_555, 100), 255);
+        assert_eq!(unlocked(2_555, 149), 255);
+        assert_eq!(unlocked(2_555, 150), 255 + 372);
+        assert_eq!(unlocked(2_555, 200), 1000);
+        assert_eq!(unlocked(10_000, 100), 1000);
+    }
+
+    #[test]
+    fn test_carried_amount_stays_unlocked() {
+        // A restarted stream keeps its earlier 400 and takes 10% of the new 600 up front
+        let unlocked =
+            calculate_unlocked_with_initial(1000, 400, 1_000, 100, 150, 200, 120, |_| {
+                panic!("nothing vests before the cliff")
+            });
+        assert_eq!(unlocked, 460);
+
+        // A curve overshooting its remainder is clamped to it
+        let unlocked =
+            calculate_unlocked_with_initial(1000, 400, 1_000, 100, 100, 200, 150, |_| 1000);
+        assert_eq!(unlocked, 1000);
+    }
+
     #[test]
     fn test_exponential_curve() {
         let total = 1000_i128;
@@ -187,6 +602,88 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_custom_curve() {
+        let env = soroban_sdk::Env::default();
+        // 10% after 10s, flat until 50s, then the rest by 100s
+        let points = soroban_sdk::vec![
+            &env,
+            CurvePoint {
+                time_offset: 10,
+                unlocked_bps: 1_000,
+            },
+            CurvePoint {
+                time_offset: 50,
+                unlocked_bps: 1_000,
+            },
+            CurvePoint {
+                time_offset: 100,
+                unlocked_bps: 10_000,
+            },
+        ];
+        let total = 1000_i128;
+
+        assert_eq!(calculate_custom_unlocked(total, &points, 0).unwrap(), 0);
+        assert_eq!(calculate_custom_unlocked(total, &points, 5).unwrap(), 50);
+        assert_eq!(calculate_custom_unlocked(total, &points, 10).unwrap(), 100);
+        assert_eq!(calculate_custom_unlocked(total, &points, 30).unwrap(), 100);
+        assert_eq!(calculate_custom_unlocked(total, &points, 75).unwrap(), 550);
+        assert_eq!(
+            calculate_custom_unlocked(total, &points, 100).unwrap(),
+            1000
+        );
+        assert_eq!(
+            calculate_custom_unlocked(total, &points, 500).unwrap(),
+            1000
+        );
+    }
+
+    #[test]
+    fn test_custom_curve_rounds_down_and_is_monotonic() {
+        let env = soroban_sdk::Env::default();
+        let points = soroban_sdk::vec![
+            &env,
+            CurvePoint {
+                time_offset: 3,
+                unlocked_bps: 3_333,
+            },
+            CurvePoint {
+                time_offset: 7,
+                unlocked_bps: 10_000,
+            },
+        ];
+        let total = 1001_i128;
+
+        let mut previous = 0;
+        for elapsed in 0..=7 {
+            let unlocked = calculate_custom_unlocked(total, &points, elapsed).unwrap();
+            assert!(unlocked >= previous);
+            previous = unlocked;
+        }
+        // 1001 * 3333 / 10_000 = 333.63, rounded down
+        assert_eq!(calculate_custom_unlocked(total, &points, 3).unwrap(), 333);
+        assert_eq!(previous, total);
+    }
+
+    #[test]
+    fn test_periodic_tranches() {
+        let total = 1000_i128;
+
+        // Three tranches of 333, the last one picks up the dust
+        assert_eq!(calculate_periodic_unlocked(total, 30, 3, 0).unwrap(), 0);
+        assert_eq!(calculate_periodic_unlocked(total, 30, 3, 29).unwrap(), 0);
+        assert_eq!(calculate_periodic_unlocked(total, 30, 3, 30).unwrap(), 333);
+        assert_eq!(calculate_periodic_unlocked(total, 30, 3, 89).unwrap(), 666);
+        assert_eq!(calculate_periodic_unlocked(total, 30, 3, 90).unwrap(), 1000);
+        assert_eq!(
+            calculate_periodic_unlocked(total, 30, 3, 500).unwrap(),
+            1000
+        );
+
+        assert!(calculate_periodic_unlocked(total, 0, 3, 10).is_err());
+        assert!(calculate_periodic_unlocked(total, 30, 0, 10).is_err());
+    }
+
     #[test]
     fn test_exponential_overflow_protection() {
         // Test with large values that could overflow
@@ -198,11 +695,174 @@ mod test {
         let result = calculate_exponential_unlocked(total, start, end, 500);
         assert!(result.is_ok());
 
-        // Test with values that will definitely overflow
+        // Values whose intermediate product exceeds i128 stay on the curve
         let huge_total = i128::MAX / 100;
-        let result_overflow = calculate_exponential_unlocked(huge_total, 0, 10, 9);
-        // Should return Err for overflow
-        assert!(result_overflow.is_err() || result_overflow.is_ok());
+        let result_huge = calculate_exponential_unlocked(huge_total, 0, 10, 9);
+        assert_eq!(result_huge, Ok(huge_total * 81 / 100));
+    }
+
+    #[test]
+    fn test_power_curve() {
+        let total = 1_000_000_000_000_i128;
+
+        // (progress, exponent, exact unlocked)
+        let cases = [
+            (50, 1_000, 500_000_000_000_i128),
+            (50, 2_000, 250_000_000_000),
+            (25, 500, 500_000_000_000),
+            (50, 3_000, 125_000_000_000),
+            (10, 10_000, 100),
+        ];
+        for (now, exponent, exact) in cases {
+            let unlocked = calculate_power_unlocked(total, 0, 100, now, exponent).unwrap();
+            // Rounds down, and only by a negligible fraction
+            assert!(unlocked <= exact);
+            assert!(exact - unlocked <= total / 1_000_000_000_000 + 1);
+        }
+
+        assert_eq!(calculate_power_unlocked(total, 10, 110, 5, 500), Ok(0));
+        assert_eq!(calculate_power_unlocked(total, 10, 110, 10, 500), Ok(0));
+        assert_eq!(
+            calculate_power_unlocked(total, 10, 110, 110, 500),
+            Ok(total)
+        );
+        assert_eq!(
+            calculate_power_unlocked(total, 10, 110, 500, 3_000),
+            Ok(total)
+        );
+        assert!(calculate_power_unlocked(total, 0, 100, 50, 0).is_err());
+    }
+
+    #[test]
+    fn test_power_curve_monotonic_and_bounded() {
+        let max = i128::MAX;
+        for exponent in [100, 333, 500, 1_000, 1_500, 2_000, 3_000, 10_000] {
+            let mut previous = 0;
+            for now in 0..=1_000 {
+                let unlocked = calculate_power_unlocked(max, 0, 1_000, now, exponent).unwrap();
+                assert!(unlocked >= previous);
+                assert!(unlocked <= max);
+                previous = unlocked;
+            }
+            assert_eq!(previous, max);
+
+            // Neighbouring seconds on a very long stream differ by tiny
+            // amounts, where truncation errors would show up first
+            let end = 1_000_000_000_000_000_000;
+            let mut previous = 0;
+            for now in (end / 3)..(end / 3 + 200) {
+                let unlocked = calculate_power_unlocked(max, 0, end, now, exponent).unwrap();
+                assert!(unlocked >= previous);
+                previous = unlocked;
+            }
+        }
+    }
+
+    #[test]
+    fn test_rate_stream_accrual() {
+        // 300 accrued at t=100, then 7 per second
+        assert_eq!(calculate_rate_streamed(300, 7, 100, 50), 300);
+        assert_eq!(calculate_rate_streamed(300, 7, 100, 110), 370);
+        // Keeps accruing past any deposit
+        assert_eq!(calculate_rate_streamed(300, 7, 100, 200), 1000);
+        assert_eq!(calculate_rate_streamed(300, 7, 100, 300), 1700);
+        assert_eq!(calculate_rate_streamed(0, i128::MAX, 0, 2), i128::MAX);
+
+        // 700 left at 7 per second: exactly 100 seconds
+        assert_eq!(calculate_depletion_time(1000, 300, 7, 100), 200);
+        // 701 left: the last unit needs a 101st second
+        assert_eq!(calculate_depletion_time(1001, 300, 7, 100), 201);
+        assert_eq!(calculate_depletion_time(1000, 1000, 7, 100), 100);
+        assert_eq!(calculate_depletion_time(i128::MAX, 0, 1, 100), u64::MAX);
+    }
+
+    #[test]
+    fn test_funded_until() {
+        // 700 at 7 per second lasts exactly 100 seconds
+        assert_eq!(calculate_funded_until(700, 7, 100), 200);
+        // 706 leaves dust that cannot pay for a 101st second
+        assert_eq!(calculate_funded_until(706, 7, 100), 200);
+        assert_eq!(calculate_funded_until(0, 7, 100), 100);
+        assert_eq!(calculate_funded_until(700, 0, 100), u64::MAX);
+        assert_eq!(calculate_funded_until(i128::MAX, 1, 100), u64::MAX);
+    }
+
+    #[test]
+    fn test_mul_div_floor() {
+        assert_eq!(mul_div_floor(7, 3, 2), Ok(10));
+        assert_eq!(mul_div_floor(6, 3, 2), Ok(9));
+        // Negative results round down, not towards zero
+        assert_eq!(mul_div_floor(-7, 3, 2), Ok(-11));
+        assert_eq!(mul_div_floor(7, 3, -2), Ok(-11));
+        assert_eq!(mul_div_floor(-6, 3, 2), Ok(-9));
+        assert_eq!(mul_div_floor(-7, -3, 2), Ok(10));
+        assert_eq!(mul_div_floor(0, -3, 2), Ok(0));
+        assert_eq!(mul_div_floor(7, 3, 0), Err(()));
+    }
+
+    #[test]
+    fn test_mul_div_floor_at_i128_extremes() {
+        let max = i128::MAX;
+        let min = i128::MIN;
+
+        assert_eq!(mul_div_floor(max, max, max), Ok(max));
+        assert_eq!(mul_div_floor(max, max - 1, max), Ok(max - 1));
+        assert_eq!(mul_div_floor(max, 2, 2), Ok(max));
+        assert_eq!(mul_div_floor(max, max, 1), Err(()));
+        assert_eq!(mul_div_floor(max, 2, 1), Err(()));
+        assert_eq!(mul_div_floor(min, 1, 1), Ok(min));
+        assert_eq!(mul_div_floor(min, min, min), Ok(min));
+        assert_eq!(mul_div_floor(min, -1, 1), Err(()));
+        // (2^127 - 1)^2 / 2^127 = 2^127 - 2 + 1/2^127, rounded down
+        assert_eq!(mul_div_floor(max, max, min), Ok(min + 1));
+        assert_eq!(mul_div_floor(max, -max, min), Ok(max - 1));
+
+        assert_eq!(
+            mul_div_floor_u128(u128::MAX, u128::MAX, u128::MAX),
+            Ok(u128::MAX)
+        );
+        assert_eq!(mul_div_floor_u128(u128::MAX, 2, 1), Err(()));
+        assert_eq!(
+            mul_div_floor_u128(u128::MAX, u128::MAX - 1, u128::MAX),
+            Ok(u128::MAX - 1)
+        );
+    }
+
+    #[test]
+    fn test_curves_exact_for_large_supply() {
+        let max = i128::MAX;
+        // 10^12 tokens with 18 decimals
+        let supply = 1_000_000_000_000_000_000_000_000_000_000_i128;
+
+        assert_eq!(calculate_unlocked_amount(max, 0, 100, 50), max / 2);
+        assert_eq!(calculate_unlocked(max, 0, 0, 3, 1), max / 3);
+        assert_eq!(calculate_exponential_unlocked(max, 0, 100, 50), Ok(max / 4));
+        assert_eq!(
+            calculate_exponential_unlocked(supply, 0, 100, 70),
+            Ok(supply / 100 * 49)
+        );
+        assert_eq!(calculate_periodic_unlocked(max, 30, 3, 30), Ok(max / 3));
+        assert_eq!(calculate_initial_unlock(max, 5_000), max / 2);
+        assert_eq!(calculate_fee(max, 1_000), max / 10);
+
+        let env = soroban_sdk::Env::default();
+        let points = soroban_sdk::vec![
+            &env,
+            CurvePoint {
+                time_offset: u64::MAX - 1,
+                unlocked_bps: 10_000,
+            },
+        ];
+        assert_eq!(
+            calculate_custom_unlocked(max, &points, u64::MAX / 2),
+            Ok(max / 2)
+        );
+
+        // Longest possible stream: squares of u64 durations still divide exactly
+        let end = u64::MAX;
+        let quarter = calculate_exponential_unlocked(supply, 0, end, end / 2).unwrap();
+        assert!(quarter < supply / 4);
+        assert!(quarter > supply / 4 - 1_000_000_000_000);
     }
 }
 
@@ -299,4 +959,122 @@ mod proofs {
         let result = calculate_unlocked(total, start, cliff, end, now);
         assert_eq!(result, 0);
     }
+
+    /// Linear vesting of the remainder, as the contract applies it
+    fn linear(start: u64, end: u64, now: u64) -> impl FnOnce(i128) -> i128 {
+        move |remainder| portion_floor(remainder, (now - start) as i128, (end - start) as i128)
+    }
+
+    /// Invariant 6: Monotonicity holds with a carried amount, an initial
+    /// unlock and a cliff
+    #[kani::proof]
+    fn proof_initial_unlock_monotonic_over_time() {
+        let total: i128 = kani::any();
+        let carried: i128 = kani::any();
+        let bps: u32 = kani::any();
+        let start: u64 = kani::any();
+        let cliff: u64 = kani::any();
+        let end: u64 = kani::any();
+        let t1: u64 = kani::any();
+        let t2: u64 = kani::any();
+
+        kani::assume(total >= 0);
+        kani::assume(carried >= 0 && carried <= total);
+        kani::assume(bps <= 10_000);
+        kani::assume(start <= cliff);
+        kani::assume(cliff < end);
+        kani::assume(t2 >= t1);
+        kani::assume(total <= i64::MAX as i128);
+
+        let r1 = calculate_unlocked_with_initial(
+            total,
+            carried,
+            bps,
+            start,
+            cliff,
+            end,
+            t1,
+            linear(start, end, t1),
+        );
+        let r2 = calculate_unlocked_with_initial(
+            total,
+            carried,
+            bps,
+            start,
+            cliff,
+            end,
+            t2,
+            linear(start, end, t2),
+        );
+        assert!(r2 >= r1);
+    }
+
+    /// Invariant 7: Terminal resolution holds with an initial unlock, for
+    /// any curve
+    #[kani::proof]
+    fn proof_initial_unlock_terminal_resolves_exactly() {
+        let total: i128 = kani::any();
+        let carried: i128 = kani::any();
+        let bps: u32 = kani::any();
+        let start: u64 = kani::any();
+        let cliff: u64 = kani::any();
+        let end: u64 = kani::any();
+        let current: u64 = kani::any();
+        let vested: i128 = kani::any();
+
+        kani::assume(total >= 0);
+        kani::assume(carried >= 0 && carried <= total);
+        kani::assume(bps <= 10_000);
+        kani::assume(start <= cliff);
+        kani::assume(cliff < end);
+        kani::assume(current >= end);
+        kani::assume(total <= i64::MAX as i128);
+
+        let result = calculate_unlocked_with_initial(
+            total,
+            carried,
+            bps,
+            start,
+            cliff,
+            end,
+            current,
+            |_| vested,
+        );
+        assert_eq!(result, total);
+    }
+
+    /// Invariant 8: The carried amount and initial unlock are available at
+    /// start, even before the cliff, and no curve pushes the result outside
+    /// `[0, total]`
+    #[kani::proof]
+    fn proof_initial_unlock_ignores_cliff() {
+        let total: i128 = kani::any();
+        let carried: i128 = kani::any();
+        let bps: u32 = kani::any();
+        let start: u64 = kani::any();
+        let cliff: u64 = kani::any();
+        let end: u64 = kani::any();
+        let now: u64 = kani::any();
+        let vested: i128 = kani::any();
+
+        kani::assume(total >= 0);
+        kani::assume(carried >= 0 && carried <= total);
+        kani::assume(bps <= 10_000);
+        kani::assume(start <= cliff);
+        kani::assume(cliff < end);
+        kani::assume(total <= i64::MAX as i128);
+
+        let result =
+            calculate_unlocked_with_initial(total, carried, bps, start, cliff, end, now, |_| {
+                vested
+            });
+        if now >= start && now < cliff {
+            assert_eq!(
+                result,
+                carried + calculate_initial_unlock(total - carried, bps)
+            );
+        }
+        assert!(result >= 0);
+        assert!(result <= total);
+    }
 }
@@ -0,0 +1,214 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::create_token_contract;
+use crate::types::{CurveType, Milestone, TopUpMode};
+use crate::{StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    vec, Address, Env, Vec,
+};
+
+fn setup_test(env: &Env) -> (StellarStreamContractClient<'_>, Address, Address, Address) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+    let receiver = Address::generate(env);
+
+    let (token, _) = create_token_contract(env, &admin);
+    StellarAssetClient::new(env, &token).mint(&sender, &10_000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+
+    (client, sender, receiver, token)
+}
+
+fn create(
+    client: &StellarStreamContractClient<'_>,
+    sender: &Address,
+    receiver: &Address,
+    token: &Address,
+    milestones: &Vec<Milestone>,
+) -> Result<u64, Error> {
+    match client.try_create_stream_with_milestones(
+        sender,
+        receiver,
+        token,
+        &1000,
+        &0,
+        &200,
+        milestones,
+        &CurveType::Linear,
+        &false,
+        &None,
+    ) {
+        Ok(Ok(stream_id)) => Ok(stream_id),
+        Err(Ok(error)) => Err(error),
+        _ => panic!("unexpected result"),
+    }
+}
+
+#[test]
+fn test_milestone_percentages_are_cumulative() {
+    let env = Env::default();
+    let (client, sender, receiver, token) = setup_test(&env);
+
+    // 40% by t=50, 70% by t=100, the last 30% streams linearly
+    let milestones = vec![
+        &env,
+        Milestone {
+            timestamp: 50,
+            percentage: 40,
+        },
+        Milestone {
+            timestamp: 100,
+            percentage: 70,
+        },
+    ];
+    let stream_id = create(&client, &sender, &receiver, &token, &milestones).unwrap();
+
+    // Step floor of 400 plus 300 * 50 / 200 streamed
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 475);
+
+    // Step floor of 700 plus 300 * 100 / 200 streamed
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 850 - 475);
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 150);
+}
+
+#[test]
+fn test_milestones_past_100_percent_rejected() {
+    let env = Env::default();
+    let (client, sender, receiver, token) = setup_test(&env);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            timestamp: 100,
+            percentage: 60,
+        },
+        Milestone {
+            timestamp: 150,
+            percentage: 101,
+        },
+    ];
+    assert_eq!(
+        create(&client, &sender, &receiver, &token, &milestones),
+        Err(Error::InvalidMilestone)
+    );
+}
+
+#[test]
+fn test_decreasing_milestones_rejected() {
+    let env = Env::default();
+    let (client, sender, receiver, token) = setup_test(&env);
+
+    // A later milestone cannot unlock less than an earlier one
+    let decreasing = vec![
+        &env,
+        Milestone {
+            timestamp: 100,
+            percentage: 60,
+        },
+        Milestone {
+            timestamp: 150,
+            percentage: 40,
+        },
+    ];
+    assert_eq!(
+        create(&client, &sender, &receiver, &token, &decreasing),
+        Err(Error::InvalidMilestone)
+    );
+
+    // Nor can milestones be listed out of date order
+    let unordered = vec![
+        &env,
+        Milestone {
+            timestamp: 150,
+            percentage: 40,
+        },
+        Milestone {
+            timestamp: 100,
+            percentage: 60,
+        },
+    ];
+    assert_eq!(
+        create(&client, &sender, &receiver, &token, &unordered),
+        Err(Error::InvalidMilestone)
+    );
+}
+
+#[test]
+fn test_milestone_outside_stream_window_rejected() {
+    let env = Env::default();
+    let (client, sender, receiver, token) = setup_test(&env);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            timestamp: 250,
+            percentage: 50,
+        },
+    ];
+    assert_eq!(
+        create(&client, &sender, &receiver, &token, &milestones),
+        Err(Error::InvalidMilestone)
+    );
+}
+
+#[test]
+fn test_increase_rate_top_up_keeps_step_schedule() {
+    let env = Env::default();
+    let (client, sender, receiver, token) = setup_test(&env);
+
+    let mut milestones = Vec::new(&env);
+    for (timestamp, percentage) in [(90, 25), (180, 50), (270, 75), (360, 100)] {
+        milestones.push_back(Milestone {
+            timestamp,
+            percentage,
+        });
+    }
+    let stream_id = client.create_stream_with_milestones(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &360,
+        &milestones,
+        &CurveType::Linear,
+        &false,
+        &None,
+    );
+
+    // Halfway through the steps, the remaining 500 plus the top-up follow
+    // the two steps left, rescaled to 50% and 100% of the new remainder
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.top_up_stream(&stream_id, &sender, &1000, &TopUpMode::IncreaseRate);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 500);
+
+    env.ledger().with_mut(|li| li.timestamp = 269);
+    assert_eq!(
+        client.try_withdraw(&stream_id, &receiver),
+        Err(Ok(Error::InsufficientBalance))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 270);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 750);
+
+    env.ledger().with_mut(|li| li.timestamp = 359);
+    assert_eq!(
+        client.try_withdraw(&stream_id, &receiver),
+        Err(Ok(Error::InsufficientBalance))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 360);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 750);
+    assert_eq!(client.get_stream(&stream_id).withdrawn_amount, 2000);
+}
@@ -0,0 +1,150 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::setup_test;
+use crate::types::{CurveType, NextUnlock, PeriodicSchedule, StreamOptions, TopUpMode};
+use soroban_sdk::{testutils::Ledger, Env};
+
+/// `tranches` equal unlocks, one every `period_secs`
+fn periodic_options(env: &Env, period_secs: u64, tranches: u32) -> StreamOptions {
+    StreamOptions {
+        curve_type: CurveType::Periodic(PeriodicSchedule {
+            period_secs,
+            tranches,
+        }),
+        ..StreamOptions::new(env)
+    }
+}
+
+fn next_unlock(timestamp: u64, amount: i128) -> Option<NextUnlock> {
+    Some(NextUnlock { timestamp, amount })
+}
+
+#[test]
+fn test_tranches_release_at_period_boundaries() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+    let options = periodic_options(&env, 30, 3);
+    let stream_id =
+        client.create_stream_with_options(&sender, &receiver, &token, &1000, &0, &90, &options);
+
+    assert_eq!(client.get_next_unlock(&stream_id), next_unlock(30, 333));
+
+    env.ledger().with_mut(|li| li.timestamp = 29);
+    assert_eq!(
+        client.try_withdraw(&stream_id, &receiver),
+        Err(Ok(Error::InsufficientBalance))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 30);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 333);
+
+    env.ledger().with_mut(|li| li.timestamp = 65);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 333);
+    // The last tranche absorbs the rounding dust
+    assert_eq!(client.get_next_unlock(&stream_id), next_unlock(90, 334));
+
+    env.ledger().with_mut(|li| li.timestamp = 90);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 334);
+    assert_eq!(token_client.balance(&receiver), 1000);
+    assert_eq!(client.get_next_unlock(&stream_id), None);
+}
+
+#[test]
+fn test_pause_delays_tranches() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+    let options = periodic_options(&env, 30, 3);
+    let stream_id =
+        client.create_stream_with_options(&sender, &receiver, &token, &1000, &0, &90, &options);
+
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    client.pause_stream(&stream_id, &sender);
+    assert_eq!(client.get_next_unlock(&stream_id), None);
+
+    env.ledger().with_mut(|li| li.timestamp = 40);
+    client.unpause_stream(&stream_id, &sender);
+    assert_eq!(client.get_next_unlock(&stream_id), next_unlock(50, 333));
+
+    env.ledger().with_mut(|li| li.timestamp = 45);
+    assert_eq!(
+        client.try_withdraw(&stream_id, &receiver),
+        Err(Ok(Error::InsufficientBalance))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 333);
+}
+
+#[test]
+fn test_top_up_is_spread_over_remaining_tranches() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+    let options = periodic_options(&env, 30, 3);
+    let stream_id =
+        client.create_stream_with_options(&sender, &receiver, &token, &1000, &0, &90, &options);
+
+    env.ledger().with_mut(|li| li.timestamp = 35);
+    client.top_up_stream(&stream_id, &sender, &500, &TopUpMode::IncreaseRate);
+    assert_eq!(client.get_stream(&stream_id).end_time, 90);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 333);
+    assert_eq!(client.get_next_unlock(&stream_id), next_unlock(60, 583));
+
+    env.ledger().with_mut(|li| li.timestamp = 90);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 1167);
+}
+
+#[test]
+fn test_tranches_before_cliff_release_at_cliff() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+    let options = StreamOptions {
+        cliff_time: Some(45),
+        ..periodic_options(&env, 30, 3)
+    };
+    let stream_id =
+        client.create_stream_with_options(&sender, &receiver, &token, &1000, &0, &90, &options);
+
+    assert_eq!(client.get_next_unlock(&stream_id), next_unlock(45, 333));
+
+    env.ledger().with_mut(|li| li.timestamp = 45);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 333);
+    assert_eq!(client.get_next_unlock(&stream_id), next_unlock(60, 333));
+}
+
+#[test]
+fn test_invalid_schedules_rejected() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    for (period_secs, tranches) in [(0, 3), (30, 0), (30, 2), (u64::MAX, 2)] {
+        let options = periodic_options(&env, period_secs, tranches);
+        assert_eq!(
+            client.try_create_stream_with_options(
+                &sender, &receiver, &token, &1000, &0, &90, &options
+            ),
+            Err(Ok(Error::InvalidCurve))
+        );
+    }
+}
+
+#[test]
+fn test_next_unlock_only_for_periodic_streams() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &90,
+        &CurveType::Linear,
+        &false,
+    );
+
+    assert_eq!(client.get_next_unlock(&stream_id), None);
+    assert_eq!(
+        client.try_get_next_unlock(&99),
+        Err(Ok(Error::StreamNotFound))
+    );
+}
@@ -0,0 +1,104 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::setup_with_balance;
+use crate::types::{CurveType, StreamOptions, TopUpMode};
+use crate::StellarStreamContractClient;
+use soroban_sdk::{testutils::Ledger, token::TokenClient, Address, Env};
+
+fn setup_test(
+    env: &Env,
+) -> (
+    StellarStreamContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    TokenClient<'_>,
+) {
+    setup_with_balance(env, 10_000_000)
+}
+
+fn power_options(env: &Env, exponent: u32) -> StreamOptions {
+    StreamOptions {
+        curve_type: CurveType::Power(exponent),
+        ..StreamOptions::new(env)
+    }
+}
+
+#[test]
+fn test_front_loaded_power_curve() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+    let options = power_options(&env, 500);
+    let stream_id = client
+        .create_stream_with_options(&sender, &receiver, &token, &1_000_000, &0, &100, &options);
+
+    // sqrt(0.25) = 0.5
+    env.ledger().with_mut(|li| li.timestamp = 25);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 500_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.withdraw(&stream_id, &receiver);
+    assert_eq!(token_client.balance(&receiver), 1_000_000);
+}
+
+#[test]
+fn test_back_loaded_power_curve() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+    let options = power_options(&env, 3_000);
+    let stream_id = client
+        .create_stream_with_options(&sender, &receiver, &token, &1_000_000, &0, &100, &options);
+
+    // 0.5^3 = 12.5%
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 125_000);
+
+    // Cancelling pays out exactly what the curve unlocked so far
+    env.ledger().with_mut(|li| li.timestamp = 80);
+    client.cancel(&stream_id, &sender);
+    assert_eq!(client.get_stream(&stream_id).withdrawn_amount, 511_999);
+}
+
+#[test]
+fn test_power_exponent_bounds() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    for exponent in [0, 99, 10_001] {
+        let options = power_options(&env, exponent);
+        assert_eq!(
+            client.try_create_stream_with_options(
+                &sender, &receiver, &token, &1000, &0, &100, &options
+            ),
+            Err(Ok(Error::InvalidCurve))
+        );
+    }
+
+    for exponent in [100, 10_000] {
+        let options = power_options(&env, exponent);
+        client.create_stream_with_options(&sender, &receiver, &token, &1000, &0, &100, &options);
+    }
+}
+
+#[test]
+fn test_increase_rate_top_up_follows_power_curve() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+    let options = power_options(&env, 2_000);
+    let stream_id = client
+        .create_stream_with_options(&sender, &receiver, &token, &1_000_000, &0, &100, &options);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 250_000);
+
+    // The other 1_750_000 follow the curve again over the last 50s
+    client.top_up_stream(&stream_id, &sender, &1_000_000, &TopUpMode::IncreaseRate);
+    assert_eq!(client.get_stream(&stream_id).end_time, 100);
+    assert!(client.try_withdraw(&stream_id, &receiver).is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = 75);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 437_500);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 1_312_500);
+}
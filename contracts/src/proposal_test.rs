@@ -0,0 +1,439 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::create_token_contract;
+use crate::types::{
+    CancelAuthority, CancellationPolicy, CurveType, Milestone, ProposalConfig,
+    ProposalRejectedEvent, StreamOptions,
+};
+use crate::{StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, IntoVal, Vec,
+};
+
+fn setup_test(
+    env: &Env,
+) -> (
+    StellarStreamContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    TokenClient<'_>,
+) {
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().with_mut(|li| li.timestamp = 50);
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+    let receiver = Address::generate(env);
+
+    let (token_address, token_client) = create_token_contract(env, &admin);
+    StellarAssetClient::new(env, &token_address).mint(&sender, &10_000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+
+    (client, sender, receiver, token_address, token_client)
+}
+
+fn voters(env: &Env, count: u32) -> Vec<Address> {
+    let mut voters = Vec::new(env);
+    for _ in 0..count {
+        voters.push_back(Address::generate(env));
+    }
+    voters
+}
+
+fn config(
+    voters: &Vec<Address>,
+    required_approvals: u32,
+    required_rejections: u32,
+) -> ProposalConfig {
+    ProposalConfig {
+        required_approvals,
+        required_rejections,
+        voters: voters.clone(),
+        deadline: 1000,
+        escrow: false,
+        council_id: None,
+        timelock: None,
+    }
+}
+
+fn escrow_config(voters: &Vec<Address>, required_approvals: u32) -> ProposalConfig {
+    ProposalConfig {
+        escrow: true,
+        ..config(voters, required_approvals, 1)
+    }
+}
+
+#[test]
+fn test_rejection_threshold_discards_proposal() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _token_client) = setup_test(&env);
+    let voters = voters(&env, 4);
+    let (rejector1, rejector2) = (voters.get(0).unwrap(), voters.get(1).unwrap());
+
+    let proposal_id = client.create_proposal_with_config(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &100,
+        &200,
+        &StreamOptions::new(&env),
+        &config(&voters, 3, 2),
+    );
+
+    client.reject_proposal(&proposal_id, &rejector1);
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.rejectors.len(), 1);
+
+    client.reject_proposal(&proposal_id, &rejector2);
+    let events = env.events().all();
+    let event = events.last().unwrap();
+    assert_eq!(
+        event.1,
+        (symbol_short!("reject"), rejector2.clone()).into_val(&env)
+    );
+    let rejected: ProposalRejectedEvent = event.2.into_val(&env);
+    assert_eq!(rejected.rejection_count, 2);
+    assert_eq!(rejected.required_rejections, 2);
+
+    let result = client.try_get_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+    let result = client.try_approve_proposal(&proposal_id, &voters.get(2).unwrap());
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+}
+
+#[test]
+fn test_only_listed_voters_can_vote() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _token_client) = setup_test(&env);
+    let voters = voters(&env, 3);
+    let outsider = Address::generate(&env);
+
+    let proposal_id = client.create_proposal_with_config(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &100,
+        &200,
+        &StreamOptions::new(&env),
+        &config(&voters, 2, 2),
+    );
+
+    let result = client.try_reject_proposal(&proposal_id, &outsider);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    let result = client.try_approve_proposal(&proposal_id, &outsider);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    assert_eq!(client.get_proposal(&proposal_id).rejectors.len(), 0);
+}
+
+#[test]
+fn test_open_proposal_cannot_be_rejected() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _token_client) = setup_test(&env);
+
+    let proposal_id =
+        client.create_proposal(&sender, &receiver, &token, &1000, &100, &200, &2, &1000);
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.required_rejections, 0);
+    assert!(proposal.voters.is_empty());
+
+    let result = client.try_reject_proposal(&proposal_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_cannot_vote_both_ways() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _token_client) = setup_test(&env);
+    let voters = voters(&env, 4);
+    let (voter1, voter2) = (voters.get(0).unwrap(), voters.get(1).unwrap());
+
+    let proposal_id = client.create_proposal_with_config(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &100,
+        &200,
+        &StreamOptions::new(&env),
+        &config(&voters, 3, 2),
+    );
+
+    client.approve_proposal(&proposal_id, &voter1);
+    let result = client.try_reject_proposal(&proposal_id, &voter1);
+    assert_eq!(result, Err(Ok(Error::AlreadyApproved)));
+
+    client.reject_proposal(&proposal_id, &voter2);
+    let result = client.try_reject_proposal(&proposal_id, &voter2);
+    assert_eq!(result, Err(Ok(Error::AlreadyRejected)));
+    let result = client.try_approve_proposal(&proposal_id, &voter2);
+    assert_eq!(result, Err(Ok(Error::AlreadyRejected)));
+}
+
+#[test]
+fn test_rejection_threshold_must_match_voters() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _token_client) = setup_test(&env);
+    let voters = voters(&env, 3);
+    let nobody = Vec::new(&env);
+
+    for invalid in [
+        config(&voters, 2, 0),
+        config(&voters, 2, 4),
+        config(&voters, 4, 1),
+        config(&nobody, 2, 1),
+    ] {
+        let result = client.try_create_proposal_with_config(
+            &sender,
+            &receiver,
+            &token,
+            &1000,
+            &100,
+            &200,
+            &StreamOptions::new(&env),
+            &invalid,
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidApprovalThreshold)));
+    }
+}
+
+#[test]
+fn test_sender_cancels_proposal() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _token_client) = setup_test(&env);
+
+    let proposal_id =
+        client.create_proposal(&sender, &receiver, &token, &1000, &100, &200, &2, &1000);
+
+    let result = client.try_cancel_proposal(&proposal_id, &receiver);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    client.cancel_proposal(&proposal_id, &sender);
+    let result = client.try_get_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+}
+
+#[test]
+fn test_executed_proposal_is_removed() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _token_client) = setup_test(&env);
+
+    let proposal_id =
+        client.create_proposal(&sender, &receiver, &token, &1000, &100, &200, &1, &1000);
+    client.approve_proposal(&proposal_id, &Address::generate(&env));
+
+    let result = client.try_get_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+    let result = client.try_cancel_proposal(&proposal_id, &sender);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+    let result = client.try_reject_proposal(&proposal_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+}
+
+#[test]
+fn test_prune_removes_only_expired_proposals() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _token_client) = setup_test(&env);
+
+    let expiring = client.create_proposal(&sender, &receiver, &token, &1000, &100, &200, &2, &500);
+    let open = client.create_proposal(&sender, &receiver, &token, &1000, &100, &200, &2, &1000);
+    let executed = client.create_proposal(&sender, &receiver, &token, &1000, &100, &200, &1, &500);
+    client.approve_proposal(&executed, &Address::generate(&env));
+
+    env.ledger().with_mut(|li| li.timestamp = 600);
+    let pruned = client.prune_expired_proposals(&vec![&env, expiring, open, executed, 99]);
+    assert_eq!(pruned, 1);
+
+    let result = client.try_get_proposal(&expiring);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+    assert_eq!(client.get_proposal(&open).deadline, 1000);
+}
+
+#[test]
+fn test_expired_proposal_cannot_be_rejected() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _token_client) = setup_test(&env);
+
+    let proposal_id =
+        client.create_proposal(&sender, &receiver, &token, &1000, &100, &200, &2, &500);
+
+    env.ledger().with_mut(|li| li.timestamp = 600);
+    let result = client.try_reject_proposal(&proposal_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::ProposalExpired)));
+}
+
+#[test]
+fn test_escrowed_proposal_locks_funds_up_front() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+    let voters = voters(&env, 2);
+
+    let proposal_id = client.create_proposal_with_config(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &100,
+        &200,
+        &StreamOptions::new(&env),
+        &escrow_config(&voters, 2),
+    );
+    assert!(client.get_proposal(&proposal_id).escrowed);
+    assert_eq!(token_client.balance(&sender), 9000);
+    assert_eq!(token_client.balance(&client.address), 1000);
+
+    // Spending the rest does not stop the proposal from executing
+    token_client.transfer(&sender, &Address::generate(&env), &9000);
+
+    client.approve_proposal(&proposal_id, &voters.get(0).unwrap());
+    client.approve_proposal(&proposal_id, &voters.get(1).unwrap());
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    assert_eq!(client.withdraw(&0, &receiver), 1000);
+}
+
+#[test]
+fn test_rejection_refunds_escrow() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+    let voters = voters(&env, 2);
+
+    let proposal_id = client.create_proposal_with_config(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &100,
+        &200,
+        &StreamOptions::new(&env),
+        &escrow_config(&voters, 2),
+    );
+    client.reject_proposal(&proposal_id, &voters.get(0).unwrap());
+
+    assert_eq!(token_client.balance(&sender), 10_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_cancellation_refunds_escrow() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+
+    let proposal_id = client.create_proposal_with_config(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &100,
+        &200,
+        &StreamOptions::new(&env),
+        &escrow_config(&voters(&env, 2), 2),
+    );
+    client.cancel_proposal(&proposal_id, &sender);
+
+    assert_eq!(token_client.balance(&sender), 10_000);
+}
+
+#[test]
+fn test_pruning_refunds_escrow() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+
+    let escrowed = client.create_proposal_with_config(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &100,
+        &200,
+        &StreamOptions::new(&env),
+        &escrow_config(&voters(&env, 2), 2),
+    );
+    let unescrowed =
+        client.create_proposal(&sender, &receiver, &token, &500, &100, &200, &2, &1000);
+    assert_eq!(token_client.balance(&sender), 9000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1001);
+    let pruned = client.prune_expired_proposals(&vec![&env, escrowed, unescrowed]);
+    assert_eq!(pruned, 2);
+    assert_eq!(token_client.balance(&sender), 10_000);
+}
+
+#[test]
+fn test_execution_creates_proposed_stream() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _token_client) = setup_test(&env);
+    let voters = voters(&env, 1);
+    let arbiter = Address::generate(&env);
+
+    let options = StreamOptions {
+        cliff_time: Some(120),
+        milestones: vec![
+            &env,
+            Milestone {
+                timestamp: 150,
+                percentage: 50,
+            },
+        ],
+        curve_type: CurveType::Exponential,
+        is_soulbound: true,
+        cancellation: CancellationPolicy {
+            authority: CancelAuthority::SenderOnly,
+            cancelable_until: Some(180),
+        },
+        initial_unlock_bps: 1_000,
+        arbiter: Some(arbiter.clone()),
+        ..StreamOptions::new(&env)
+    };
+    let proposal_id = client.create_proposal_with_config(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &100,
+        &200,
+        &options,
+        &config(&voters, 1, 1),
+    );
+    assert_eq!(client.get_proposal(&proposal_id).options, options);
+
+    client.approve_proposal(&proposal_id, &voters.get(0).unwrap());
+
+    let stream = client.get_stream(&0);
+    assert_eq!(stream.cliff_time, 120);
+    assert_eq!(stream.milestones, options.milestones);
+    assert_eq!(stream.curve_type, CurveType::Exponential);
+    assert!(stream.is_soulbound);
+    assert_eq!(stream.cancellation, options.cancellation);
+    assert_eq!(stream.initial_unlock_bps, 1_000);
+    assert_eq!(stream.arbiter, Some(arbiter));
+}
+
+#[test]
+fn test_invalid_options_rejected_at_proposal() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _token_client) = setup_test(&env);
+
+    let options = StreamOptions {
+        initial_unlock_bps: 10_001,
+        ..StreamOptions::new(&env)
+    };
+    let result = client.try_create_proposal_with_config(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &100,
+        &200,
+        &options,
+        &config(&voters(&env, 1), 1, 1),
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidInitialUnlock)));
+}
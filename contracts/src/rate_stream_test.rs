@@ -0,0 +1,263 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::setup_test;
+use crate::types::{CurveType, StreamInsolventEvent, StreamSolventEvent, TopUpMode};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Events, Ledger},
+    Env, IntoVal,
+};
+
+#[test]
+fn test_rate_stream_accrues_until_balance_runs_out() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+
+    // 1000 at 3 per second lasts 333.33s
+    let stream_id = client.create_rate_stream(&sender, &receiver, &token, &3, &1000, &0);
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.total_amount, 1000);
+    assert_eq!(stream.end_time, 334);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 300);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 700);
+    assert_eq!(token_client.balance(&receiver), 1000);
+}
+
+#[test]
+fn test_top_up_pays_debt_first() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let stream_id = client.create_rate_stream(&sender, &receiver, &token, &10, &1000, &0);
+
+    // Ran dry at 100 and kept accruing: 500 owed by 150
+    env.ledger().with_mut(|li| li.timestamp = 150);
+    assert_eq!(client.get_covered_debt(&stream_id), 1000);
+    assert_eq!(client.get_uncovered_debt(&stream_id), 500);
+    assert_eq!(client.get_depletion_time(&stream_id), 100);
+
+    // Only part of the debt is covered; the stream still ran dry at 100
+    client.top_up_stream(&stream_id, &sender, &300, &TopUpMode::ExtendDuration);
+    assert_eq!(client.get_uncovered_debt(&stream_id), 200);
+    assert_eq!(client.get_depletion_time(&stream_id), 100);
+
+    client.top_up_stream(&stream_id, &sender, &700, &TopUpMode::ExtendDuration);
+    assert_eq!(client.get_uncovered_debt(&stream_id), 0);
+    assert_eq!(client.get_depletion_time(&stream_id), 200);
+
+    env.ledger().with_mut(|li| li.timestamp = 170);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 1700);
+    assert_eq!(client.get_covered_debt(&stream_id), 0);
+}
+
+#[test]
+fn test_solvency_events() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let stream_id = client.create_rate_stream(&sender, &receiver, &token, &10, &1000, &0);
+
+    // Noticed on the first call after the balance runs out
+    env.ledger().with_mut(|li| li.timestamp = 120);
+    client.withdraw(&stream_id, &receiver);
+    let insolvent_topics = (symbol_short!("insolvent"), stream_id).into_val(&env);
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| *topics == insolvent_topics)
+        .unwrap();
+    let insolvent: StreamInsolventEvent = data.into_val(&env);
+    assert_eq!(insolvent.uncovered_debt, 200);
+    assert_eq!(insolvent.depleted_at, 100);
+
+    client.top_up_stream(&stream_id, &sender, &1000, &TopUpMode::ExtendDuration);
+    let solvent_topics = (symbol_short!("solvent"), stream_id).into_val(&env);
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| *topics == solvent_topics)
+        .unwrap();
+    let solvent: StreamSolventEvent = data.into_val(&env);
+    assert_eq!(solvent.new_end_time, 200);
+}
+
+#[test]
+fn test_debt_views_require_rate_stream() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let fixed_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(
+        client.try_get_uncovered_debt(&fixed_id),
+        Err(Ok(Error::NotRateStream))
+    );
+    assert_eq!(
+        client.try_get_depletion_time(&fixed_id),
+        Err(Ok(Error::NotRateStream))
+    );
+}
+
+#[test]
+fn test_update_rate_applies_from_now() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let stream_id = client.create_rate_stream(&sender, &receiver, &token, &10, &1000, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    client.update_stream_rate(&stream_id, &sender, &20);
+
+    // 200 accrued at the old rate, the remaining 800 at 20/s
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.end_time, 60);
+
+    env.ledger().with_mut(|li| li.timestamp = 30);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 400);
+}
+
+#[test]
+fn test_rate_stream_start_time_validated() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(
+        client.try_create_rate_stream(&sender, &receiver, &token, &10, &1000, &99),
+        Err(Ok(Error::InvalidTimeRange))
+    );
+
+    // A start this late leaves no room for the deposit to stream
+    assert_eq!(
+        client.try_create_rate_stream(&sender, &receiver, &token, &10, &1000, &u64::MAX),
+        Err(Ok(Error::InvalidTimeRange))
+    );
+    assert_eq!(token_client.balance(&sender), 10_000);
+
+    let stream_id = client.create_rate_stream(&sender, &receiver, &token, &10, &1000, &100);
+    assert_eq!(client.get_stream(&stream_id).end_time, 200);
+}
+
+#[test]
+fn test_update_rate_rejections() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let stream_id = client.create_rate_stream(&sender, &receiver, &token, &10, &1000, &0);
+    assert_eq!(
+        client.try_update_stream_rate(&stream_id, &receiver, &20),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_update_stream_rate(&stream_id, &sender, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    let fixed_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(
+        client.try_update_stream_rate(&fixed_id, &sender, &20),
+        Err(Ok(Error::NotRateStream))
+    );
+
+    client.cancel(&stream_id, &receiver);
+    assert_eq!(
+        client.try_update_stream_rate(&stream_id, &sender, &20),
+        Err(Ok(Error::AlreadyCancelled))
+    );
+}
+
+#[test]
+fn test_paused_rate_stream_stops_accruing() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let stream_id = client.create_rate_stream(&sender, &receiver, &token, &10, &1000, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 30);
+    client.pause_stream(&stream_id, &sender);
+    env.ledger().with_mut(|li| li.timestamp = 80);
+    client.unpause_stream(&stream_id, &sender);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.end_time, 150);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 500);
+}
+
+#[test]
+fn test_cancel_rate_stream_refunds_unstreamed_balance() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+
+    let stream_id = client.create_rate_stream(&sender, &receiver, &token, &10, &1000, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 25);
+    client.cancel(&stream_id, &sender);
+
+    assert_eq!(token_client.balance(&receiver), 250);
+    assert_eq!(token_client.balance(&sender), 9_750);
+}
+
+#[test]
+fn test_sender_cannot_cancel_insolvent_rate_stream() {
+    let env = Env::default();
+    let (client, sender, receiver, token, token_client) = setup_test(&env);
+
+    let stream_id = client.create_rate_stream(&sender, &receiver, &token, &10, &1000, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 150);
+    assert_eq!(client.get_uncovered_debt(&stream_id), 500);
+    assert_eq!(
+        client.try_cancel(&stream_id, &sender),
+        Err(Ok(Error::OutstandingDebt))
+    );
+    assert_eq!(client.get_uncovered_debt(&stream_id), 500);
+
+    // Settling the debt lets the sender cancel, and the receiver is paid in full
+    client.top_up_stream(&stream_id, &sender, &500, &TopUpMode::ExtendDuration);
+    assert_eq!(client.get_uncovered_debt(&stream_id), 0);
+    client.cancel(&stream_id, &sender);
+
+    assert_eq!(token_client.balance(&receiver), 1500);
+    assert_eq!(token_client.balance(&sender), 8_500);
+}
+
+#[test]
+fn test_increase_rate_top_up_keeps_depletion_time() {
+    let env = Env::default();
+    let (client, sender, receiver, token, _) = setup_test(&env);
+
+    let stream_id = client.create_rate_stream(&sender, &receiver, &token, &10, &1000, &0);
+
+    // 500 left plus 500 more, still running out at 100
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    client.top_up_stream(&stream_id, &sender, &500, &TopUpMode::IncreaseRate);
+    assert_eq!(client.get_stream(&stream_id).end_time, 100);
+
+    env.ledger().with_mut(|li| li.timestamp = 75);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 1000);
+}
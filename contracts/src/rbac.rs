@@ -633,9 +633,9 @@ mod tests {
         let addr = Address::generate(&env);
 
         // Test that has_role returns false when role has no members
-        assert_eq!(client.test_has_role(&Role::SuperAdmin, &addr), false);
-        assert_eq!(client.test_has_role(&Role::FinancialOperator, &addr), false);
-        assert_eq!(client.test_has_role(&Role::Guardian, &addr), false);
+        assert!(!client.test_has_role(&Role::SuperAdmin, &addr));
+        assert!(!client.test_has_role(&Role::FinancialOperator, &addr));
+        assert!(!client.test_has_role(&Role::Guardian, &addr));
     }
 
     #[test]
@@ -654,8 +654,8 @@ mod tests {
         client.test_set_role_members(&Role::SuperAdmin, &members);
 
         // Test that has_role returns true for addresses in the role
-        assert_eq!(client.test_has_role(&Role::SuperAdmin, &addr1), true);
-        assert_eq!(client.test_has_role(&Role::SuperAdmin, &addr2), true);
+        assert!(client.test_has_role(&Role::SuperAdmin, &addr1));
+        assert!(client.test_has_role(&Role::SuperAdmin, &addr2));
     }
 
     #[test]
@@ -675,7 +675,7 @@ mod tests {
         client.test_set_role_members(&Role::SuperAdmin, &members);
 
         // Test that has_role returns false for address not in the role
-        assert_eq!(client.test_has_role(&Role::SuperAdmin, &addr3), false);
+        assert!(!client.test_has_role(&Role::SuperAdmin, &addr3));
     }
 
     #[test]
@@ -692,9 +692,9 @@ mod tests {
         client.test_set_role_members(&Role::SuperAdmin, &members);
 
         // Test that address in one role doesn't appear in other roles
-        assert_eq!(client.test_has_role(&Role::SuperAdmin, &addr), true);
-        assert_eq!(client.test_has_role(&Role::FinancialOperator, &addr), false);
-        assert_eq!(client.test_has_role(&Role::Guardian, &addr), false);
+        assert!(client.test_has_role(&Role::SuperAdmin, &addr));
+        assert!(!client.test_has_role(&Role::FinancialOperator, &addr));
+        assert!(!client.test_has_role(&Role::Guardian, &addr));
     }
 
     #[test]
@@ -766,9 +766,9 @@ mod tests {
         client.test_add_role_member(&Role::Guardian, &addr);
 
         // Verify address exists in all three roles
-        assert_eq!(client.test_has_role(&Role::SuperAdmin, &addr), true);
-        assert_eq!(client.test_has_role(&Role::FinancialOperator, &addr), true);
-        assert_eq!(client.test_has_role(&Role::Guardian, &addr), true);
+        assert!(client.test_has_role(&Role::SuperAdmin, &addr));
+        assert!(client.test_has_role(&Role::FinancialOperator, &addr));
+        assert!(client.test_has_role(&Role::Guardian, &addr));
     }
 
     #[test]
@@ -817,7 +817,7 @@ mod tests {
         assert_eq!(members.len(), 2);
         assert_eq!(members.get(0).unwrap(), addr1);
         assert_eq!(members.get(1).unwrap(), addr3);
-        assert_eq!(client.test_has_role(&Role::Guardian, &addr2), false);
+        assert!(!client.test_has_role(&Role::Guardian, &addr2));
     }
 
     #[test]
@@ -843,10 +843,7 @@ mod tests {
         assert_eq!(members.len(), 2);
         assert_eq!(members.get(0).unwrap(), addr2);
         assert_eq!(members.get(1).unwrap(), addr3);
-        assert_eq!(
-            client.test_has_role(&Role::FinancialOperator, &addr1),
-            false
-        );
+        assert!(!client.test_has_role(&Role::FinancialOperator, &addr1));
     }
 
     #[test]
@@ -872,7 +869,7 @@ mod tests {
         assert_eq!(members.len(), 2);
         assert_eq!(members.get(0).unwrap(), addr1);
         assert_eq!(members.get(1).unwrap(), addr2);
-        assert_eq!(client.test_has_role(&Role::Guardian, &addr3), false);
+        assert!(!client.test_has_role(&Role::Guardian, &addr3));
     }
 
     #[test]
@@ -964,9 +961,9 @@ mod tests {
         client.test_remove_role_member(&Role::SuperAdmin, &addr);
 
         // Verify address removed from SuperAdmin but still in other roles
-        assert_eq!(client.test_has_role(&Role::SuperAdmin, &addr), false);
-        assert_eq!(client.test_has_role(&Role::FinancialOperator, &addr), true);
-        assert_eq!(client.test_has_role(&Role::Guardian, &addr), true);
+        assert!(!client.test_has_role(&Role::SuperAdmin, &addr));
+        assert!(client.test_has_role(&Role::FinancialOperator, &addr));
+        assert!(client.test_has_role(&Role::Guardian, &addr));
     }
 
     #[test]
@@ -2062,9 +2059,9 @@ mod tests {
         let addr = Address::generate(&env);
 
         // Test that has_role returns false when role has no members
-        assert_eq!(client.has_role(&Role::SuperAdmin, &addr), false);
-        assert_eq!(client.has_role(&Role::FinancialOperator, &addr), false);
-        assert_eq!(client.has_role(&Role::Guardian, &addr), false);
+        assert!(!client.has_role(&Role::SuperAdmin, &addr));
+        assert!(!client.has_role(&Role::FinancialOperator, &addr));
+        assert!(!client.has_role(&Role::Guardian, &addr));
     }
 
     #[test]
@@ -2087,12 +2084,9 @@ mod tests {
         client.add_role(&super_admin, &Role::Guardian, &guardian);
 
         // Test that has_role returns true for addresses in their respective roles
-        assert_eq!(client.has_role(&Role::SuperAdmin, &super_admin), true);
-        assert_eq!(
-            client.has_role(&Role::FinancialOperator, &financial_op),
-            true
-        );
-        assert_eq!(client.has_role(&Role::Guardian, &guardian), true);
+        assert!(client.has_role(&Role::SuperAdmin, &super_admin));
+        assert!(client.has_role(&Role::FinancialOperator, &financial_op));
+        assert!(client.has_role(&Role::Guardian, &guardian));
     }
 
     #[test]
@@ -2114,11 +2108,8 @@ mod tests {
         client.add_role(&super_admin, &Role::FinancialOperator, &financial_op);
 
         // Test that has_role returns false for address not in the role
-        assert_eq!(
-            client.has_role(&Role::FinancialOperator, &non_member),
-            false
-        );
-        assert_eq!(client.has_role(&Role::Guardian, &super_admin), false);
+        assert!(!client.has_role(&Role::FinancialOperator, &non_member));
+        assert!(!client.has_role(&Role::Guardian, &super_admin));
     }
 
     #[test]
@@ -2139,12 +2130,9 @@ mod tests {
         client.add_role(&super_admin, &Role::FinancialOperator, &financial_op);
 
         // Test that address in one role doesn't appear in other roles
-        assert_eq!(
-            client.has_role(&Role::FinancialOperator, &financial_op),
-            true
-        );
-        assert_eq!(client.has_role(&Role::SuperAdmin, &financial_op), false);
-        assert_eq!(client.has_role(&Role::Guardian, &financial_op), false);
+        assert!(client.has_role(&Role::FinancialOperator, &financial_op));
+        assert!(!client.has_role(&Role::SuperAdmin, &financial_op));
+        assert!(!client.has_role(&Role::Guardian, &financial_op));
     }
 
     #[test]
@@ -2165,13 +2153,13 @@ mod tests {
         client.add_role(&super_admin, &Role::Guardian, &guardian);
 
         // Verify guardian has the role
-        assert_eq!(client.has_role(&Role::Guardian, &guardian), true);
+        assert!(client.has_role(&Role::Guardian, &guardian));
 
         // Remove guardian
         client.remove_role(&super_admin, &Role::Guardian, &guardian);
 
         // Verify guardian no longer has the role
-        assert_eq!(client.has_role(&Role::Guardian, &guardian), false);
+        assert!(!client.has_role(&Role::Guardian, &guardian));
     }
 
     #[test]
@@ -2192,11 +2180,8 @@ mod tests {
         client.add_role(&super_admin, &Role::Guardian, &super_admin);
 
         // Verify super admin has all three roles
-        assert_eq!(client.has_role(&Role::SuperAdmin, &super_admin), true);
-        assert_eq!(
-            client.has_role(&Role::FinancialOperator, &super_admin),
-            true
-        );
-        assert_eq!(client.has_role(&Role::Guardian, &super_admin), true);
+        assert!(client.has_role(&Role::SuperAdmin, &super_admin));
+        assert!(client.has_role(&Role::FinancialOperator, &super_admin));
+        assert!(client.has_role(&Role::Guardian, &super_admin));
     }
 }
@@ -1,17 +1,17 @@
 #![cfg(test)]
 
-use crate::{Role, StellarStream, StellarStreamClient};
+use crate::{Role, StellarStreamContract, StellarStreamContractClient};
 use soroban_sdk::{testutils::Address as _, Address, Env};
 
-fn setup_test() -> (Env, Address, StellarStreamClient<'static>) {
+fn setup_test() -> (Env, Address, StellarStreamContractClient<'static>) {
     let env = Env::default();
     env.mock_all_auths();
 
     let admin = Address::generate(&env);
 
     // Deploy contract
-    let contract_id = env.register(StellarStream, ());
-    let client = StellarStreamClient::new(&env, &contract_id);
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
 
     // Initialize with admin (grants all roles)
     client.initialize(&admin);
@@ -21,7 +21,7 @@ fn setup_test() -> (Env, Address, StellarStreamClient<'static>) {
 
 #[test]
 fn test_initialize_grants_all_roles() {
-    let (env, admin, client) = setup_test();
+    let (_env, admin, client) = setup_test();
 
     // Verify admin has all roles
     assert!(client.check_role(&admin, &Role::Admin));
@@ -64,9 +64,9 @@ fn test_admin_can_revoke_roles() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only Admin can grant roles")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_non_admin_cannot_grant_roles() {
-    let (env, admin, client) = setup_test();
+    let (env, _admin, client) = setup_test();
 
     let non_admin = Address::generate(&env);
     let target = Address::generate(&env);
@@ -76,7 +76,7 @@ fn test_non_admin_cannot_grant_roles() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only Admin can revoke roles")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_non_admin_cannot_revoke_roles() {
     let (env, admin, client) = setup_test();
 
@@ -105,9 +105,9 @@ fn test_pauser_can_pause() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only Pauser can pause/unpause")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_non_pauser_cannot_pause() {
-    let (env, admin, client) = setup_test();
+    let (env, _admin, client) = setup_test();
 
     let non_pauser = Address::generate(&env);
 
@@ -127,9 +127,9 @@ fn test_treasury_manager_can_update_fee() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only TreasuryManager can update fee")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_non_treasury_manager_cannot_update_fee() {
-    let (env, admin, client) = setup_test();
+    let (env, _admin, client) = setup_test();
 
     let non_manager = Address::generate(&env);
 
@@ -151,9 +151,9 @@ fn test_treasury_manager_can_update_treasury() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only TreasuryManager can update treasury")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_non_treasury_manager_cannot_update_treasury() {
-    let (env, admin, client) = setup_test();
+    let (env, _admin, client) = setup_test();
 
     let non_manager = Address::generate(&env);
     let new_treasury = Address::generate(&env);
@@ -163,22 +163,19 @@ fn test_non_treasury_manager_cannot_update_treasury() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only TreasuryManager can update fee")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_pauser_cannot_update_fees() {
     let (env, admin, client) = setup_test();
 
     let pauser = Address::generate(&env);
     client.grant_role(&admin, &pauser, &Role::Pauser);
 
-    // Pauser can pause
-    client.set_pause(&pauser, &true);
-
-    // But pauser cannot update fees (should panic)
+    // Pauser cannot update fees (should panic)
     client.update_fee(&pauser, &200);
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only Pauser can pause/unpause")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_treasury_manager_cannot_pause() {
     let (env, admin, client) = setup_test();
 
@@ -227,8 +224,8 @@ fn test_address_can_have_multiple_roles() {
     assert!(client.check_role(&multi_role, &Role::TreasuryManager));
 
     // Can perform both actions
-    client.set_pause(&multi_role, &true);
     client.update_fee(&multi_role, &150);
+    client.set_pause(&multi_role, &true);
 }
 
 #[test]
@@ -241,12 +238,12 @@ fn test_role_separation() {
     client.grant_role(&admin, &pauser, &Role::Pauser);
     client.grant_role(&admin, &treasury_manager, &Role::TreasuryManager);
 
-    // Pauser can pause but not manage treasury
-    client.set_pause(&pauser, &true);
-
     // Treasury manager can manage fees but not pause
     client.update_fee(&treasury_manager, &250);
 
+    // Pauser can pause but not manage treasury
+    client.set_pause(&pauser, &true);
+
     // Verify role separation
     assert!(client.check_role(&pauser, &Role::Pauser));
     assert!(!client.check_role(&pauser, &Role::TreasuryManager));
@@ -259,17 +256,18 @@ fn test_admin_retains_all_permissions() {
     let (env, admin, client) = setup_test();
 
     // Admin can do everything
-    client.set_pause(&admin, &true);
     client.update_fee(&admin, &300);
 
     let new_admin = Address::generate(&env);
     client.grant_role(&admin, &new_admin, &Role::Admin);
 
+    client.set_pause(&admin, &true);
+
     // All should succeed
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: Only Pauser can pause/unpause")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_revoked_role_loses_permissions() {
     let (env, admin, client) = setup_test();
 
@@ -0,0 +1,211 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::create_token_contract;
+use crate::types::CurveType;
+use crate::{StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env,
+};
+
+struct ReceiptTestContext<'a> {
+    client: StellarStreamContractClient<'a>,
+    admin: Address,
+    sender: Address,
+    receiver: Address,
+    token: Address,
+    token_client: TokenClient<'a>,
+}
+
+fn setup_test(env: &Env) -> ReceiptTestContext<'_> {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+    let receiver = Address::generate(env);
+
+    let (token, token_client) = create_token_contract(env, &admin);
+    StellarAssetClient::new(env, &token).mint(&sender, &10_000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+    client.initialize(&admin);
+
+    ReceiptTestContext {
+        client,
+        admin,
+        sender,
+        receiver,
+        token,
+        token_client,
+    }
+}
+
+fn create(ctx: &ReceiptTestContext, is_soulbound: bool) -> u64 {
+    ctx.client.create_stream(
+        &ctx.sender,
+        &ctx.receiver,
+        &ctx.token,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &is_soulbound,
+    )
+}
+
+#[test]
+fn test_receipt_owner_holds_withdrawal_rights() {
+    let env = Env::default();
+    let ctx = setup_test(&env);
+    let stream_id = create(&ctx, false);
+    let buyer = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 30);
+    ctx.client.withdraw(&stream_id, &ctx.receiver);
+    ctx.client
+        .transfer_receipt(&stream_id, &ctx.receiver, &buyer);
+
+    assert_eq!(ctx.client.get_receipt(&stream_id).owner, buyer);
+    assert_eq!(ctx.client.get_stream(&stream_id).receipt_owner, buyer);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(
+        ctx.client.try_withdraw(&stream_id, &ctx.receiver),
+        Err(Ok(Error::NotReceiptOwner))
+    );
+    assert_eq!(ctx.client.withdraw(&stream_id, &buyer), 200);
+    assert_eq!(ctx.token_client.balance(&ctx.receiver), 300);
+    assert_eq!(ctx.token_client.balance(&buyer), 200);
+
+    // Cancellation pays the unlocked remainder to the receipt owner
+    env.ledger().with_mut(|li| li.timestamp = 60);
+    ctx.client.cancel(&stream_id, &ctx.sender);
+    assert_eq!(ctx.token_client.balance(&buyer), 300);
+    assert_eq!(ctx.token_client.balance(&ctx.sender), 9400);
+}
+
+#[test]
+fn test_receiver_index_follows_receipt() {
+    let env = Env::default();
+    let ctx = setup_test(&env);
+    let stream_id = create(&ctx, false);
+    let buyer = Address::generate(&env);
+
+    ctx.client
+        .transfer_receipt(&stream_id, &ctx.receiver, &buyer);
+
+    assert_eq!(ctx.client.get_receiver_stream_count(&ctx.receiver), 0);
+    assert_eq!(
+        ctx.client
+            .get_streams_by_receiver(&buyer, &0, &10)
+            .stream_ids,
+        vec![&env, stream_id]
+    );
+}
+
+#[test]
+fn test_only_receipt_owner_can_transfer() {
+    let env = Env::default();
+    let ctx = setup_test(&env);
+    let stream_id = create(&ctx, false);
+    let buyer = Address::generate(&env);
+
+    assert_eq!(
+        ctx.client
+            .try_transfer_receipt(&stream_id, &ctx.sender, &buyer),
+        Err(Ok(Error::NotReceiptOwner))
+    );
+
+    ctx.client
+        .transfer_receipt(&stream_id, &ctx.receiver, &buyer);
+    assert_eq!(
+        ctx.client
+            .try_transfer_receipt(&stream_id, &ctx.receiver, &ctx.receiver),
+        Err(Ok(Error::NotReceiptOwner))
+    );
+}
+
+#[test]
+fn test_soulbound_receipt_cannot_be_transferred() {
+    let env = Env::default();
+    let ctx = setup_test(&env);
+    let stream_id = create(&ctx, true);
+
+    assert_eq!(
+        ctx.client
+            .try_transfer_receipt(&stream_id, &ctx.receiver, &Address::generate(&env)),
+        Err(Ok(Error::StreamIsSoulbound))
+    );
+}
+
+#[test]
+fn test_restricted_address_cannot_take_the_claim() {
+    let env = Env::default();
+    let ctx = setup_test(&env);
+    let stream_id = create(&ctx, false);
+    let sanctioned = Address::generate(&env);
+
+    ctx.client.restrict_address(&ctx.admin, &sanctioned);
+    assert!(ctx.client.is_address_restricted(&sanctioned));
+
+    assert_eq!(
+        ctx.client
+            .try_transfer_receipt(&stream_id, &ctx.receiver, &sanctioned),
+        Err(Ok(Error::AddressRestricted))
+    );
+    assert_eq!(
+        ctx.client
+            .try_transfer_receiver(&stream_id, &ctx.sender, &sanctioned),
+        Err(Ok(Error::AddressRestricted))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(
+        ctx.client
+            .try_withdraw_amount(&stream_id, &ctx.receiver, &100, &sanctioned),
+        Err(Ok(Error::AddressRestricted))
+    );
+
+    ctx.client.unrestrict_address(&ctx.admin, &sanctioned);
+    ctx.client
+        .transfer_receipt(&stream_id, &ctx.receiver, &sanctioned);
+}
+
+#[test]
+fn test_transfer_receiver_reissues_receipt() {
+    let env = Env::default();
+    let ctx = setup_test(&env);
+    let stream_id = create(&ctx, false);
+    let new_receiver = Address::generate(&env);
+
+    ctx.client
+        .transfer_receiver(&stream_id, &ctx.sender, &new_receiver);
+
+    assert_eq!(ctx.client.get_receipt(&stream_id).owner, new_receiver);
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(ctx.client.withdraw(&stream_id, &new_receiver), 500);
+}
+
+#[test]
+fn test_sender_cannot_redirect_after_receipt_transfer() {
+    let env = Env::default();
+    let ctx = setup_test(&env);
+    let stream_id = create(&ctx, false);
+    let buyer = Address::generate(&env);
+    let new_receiver = Address::generate(&env);
+
+    ctx.client
+        .transfer_receipt(&stream_id, &ctx.receiver, &buyer);
+
+    assert_eq!(
+        ctx.client
+            .try_transfer_receiver(&stream_id, &ctx.sender, &new_receiver),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(ctx.client.get_receipt(&stream_id).owner, buyer);
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(ctx.client.withdraw(&stream_id, &buyer), 500);
+}
@@ -3,23 +3,17 @@
 use crate::{StellarStreamContract, StellarStreamContractClient};
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
-    token::{StellarAssetClient, TokenClient},
+    token::StellarAssetClient,
     Address, Env,
 };
 
 use crate::errors::Error;
+use crate::test_utils::create_token_contract;
 use crate::types::CurveType;
 
 const PRINCIPAL: i128 = 1_000_000;
 const DURATION: u64 = 86_400 * 30; // 30 days
 
-fn create_token_contract<'a>(env: &Env, admin: &Address) -> (Address, TokenClient<'a>) {
-    let contract_id = env
-        .register_stellar_asset_contract_v2(admin.clone())
-        .address();
-    (contract_id.clone(), TokenClient::new(env, &contract_id))
-}
-
 /// # Purpose
 /// Verify that a stream can be created with is_soulbound: true
 /// # Setup
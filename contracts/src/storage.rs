@@ -1,7 +1,15 @@
-use soroban_sdk::{symbol_short, Symbol};
+use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
+
+use crate::index;
+use crate::types::{
+    CancelAuthority, CancellationPolicy, Council, CouncilUpdate, CurveType, DataKey, SenderDeposit,
+    Stream, StreamAmendment, StreamIndex, StreamOptions, StreamProposal, StreamProposalV1,
+    StreamRate, StreamReceipt, StreamV1,
+};
 
 pub const STREAM_COUNT: Symbol = symbol_short!("STR_CNT");
 pub const PROPOSAL_COUNT: Symbol = symbol_short!("PROP_CNT");
+pub const COUNCIL_COUNT: Symbol = symbol_short!("CNCL_CNT");
 pub const RECEIPT: Symbol = symbol_short!("RECEIPT");
 pub const RESTRICTED_ADDRESSES: Symbol = symbol_short!("RESTRICT");
 #[allow(dead_code)]
@@ -10,3 +18,346 @@ pub const FLASH_LOAN_LOCK: Symbol = symbol_short!("FL_LOCK");
 pub const FLASH_LOAN_FEE: Symbol = symbol_short!("FL_FEE");
 #[allow(dead_code)]
 pub const REQUEST_COUNT: Symbol = symbol_short!("REQ_CNT");
+
+/// Ledgers close roughly every 5 seconds
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+/// Entries are bumped back to a year of TTL once they drop below 30 days
+pub const TTL_THRESHOLD: u32 = DAY_IN_LEDGERS * 30;
+pub const TTL_EXTEND_TO: u32 = DAY_IN_LEDGERS * 365;
+
+/// Id recorded under `DataKey::MigrationExecuted` once every stream created
+/// before per-stream storage has been moved out of the contract instance
+pub const STREAM_STORAGE_MIGRATION: u32 = 1;
+
+pub fn extend_instance_ttl(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Load a stream from its own persistent entry, extending its TTL.
+/// Streams that still sit in the contract instance are migrated on first access.
+pub fn load_stream(env: &Env, stream_id: u64) -> Option<Stream> {
+    let key = DataKey::Stream(stream_id);
+    match env.storage().persistent().get::<_, Stream>(&key) {
+        Some(stream) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+            Some(stream)
+        }
+        None => migrate_legacy_stream(env, stream_id),
+    }
+}
+
+pub fn save_stream(env: &Env, stream_id: u64, stream: &Stream) {
+    let key = DataKey::Stream(stream_id);
+    env.storage().persistent().set(&key, stream);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Load a sender's shared deposit for `token`, empty if none was made yet
+pub fn load_deposit(env: &Env, sender: &Address, token: &Address) -> SenderDeposit {
+    let key = DataKey::SenderDeposit(sender.clone(), token.clone());
+    match env.storage().persistent().get::<_, SenderDeposit>(&key) {
+        Some(deposit) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+            deposit
+        }
+        None => SenderDeposit {
+            balance: 0,
+            outgoing_rate: 0,
+            checkpoint_time: env.ledger().timestamp(),
+            streams: Vec::new(env),
+        },
+    }
+}
+
+pub fn save_deposit(env: &Env, sender: &Address, token: &Address, deposit: &SenderDeposit) {
+    let key = DataKey::SenderDeposit(sender.clone(), token.clone());
+    env.storage().persistent().set(&key, deposit);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn load_amendment(env: &Env, stream_id: u64) -> Option<StreamAmendment> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Amendment(stream_id))
+}
+
+pub fn save_amendment(env: &Env, stream_id: u64, amendment: &StreamAmendment) {
+    let key = DataKey::Amendment(stream_id);
+    env.storage().persistent().set(&key, amendment);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn remove_amendment(env: &Env, stream_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Amendment(stream_id));
+}
+
+/// Vault shares held for a stream, zero once all were redeemed
+pub fn load_vault_shares(env: &Env, stream_id: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::VaultShares(stream_id))
+        .unwrap_or(0)
+}
+
+pub fn save_vault_shares(env: &Env, stream_id: u64, shares: i128) {
+    let key = DataKey::VaultShares(stream_id);
+    if shares <= 0 {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &shares);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn load_voting_delegate(env: &Env, stream_id: u64) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::VotingDelegate(stream_id))
+}
+
+pub fn save_voting_delegate(env: &Env, stream_id: u64, delegate: &Address) {
+    let key = DataKey::VotingDelegate(stream_id);
+    env.storage().persistent().set(&key, delegate);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn remove_voting_delegate(env: &Env, stream_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::VotingDelegate(stream_id));
+}
+
+/// Load a proposal. Proposals stored in the previous layout are migrated on
+/// first access.
+pub fn load_proposal(env: &Env, proposal_id: u64) -> Option<StreamProposal> {
+    let key = DataKey::Proposal(proposal_id);
+    match env.storage().persistent().get::<_, StreamProposal>(&key) {
+        Some(proposal) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+            Some(proposal)
+        }
+        None => migrate_legacy_proposal(env, proposal_id),
+    }
+}
+
+pub fn save_proposal(env: &Env, proposal_id: u64, proposal: &StreamProposal) {
+    let key = DataKey::Proposal(proposal_id);
+    env.storage().persistent().set(&key, proposal);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn remove_proposal(env: &Env, proposal_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Proposal(proposal_id));
+}
+
+/// Move a proposal stored in the previous layout out of the contract instance
+/// into its own persistent entry.
+/// Executed proposals are dropped. Returns `None` when there is no legacy
+/// entry for the id or it was executed.
+fn migrate_legacy_proposal(env: &Env, proposal_id: u64) -> Option<StreamProposal> {
+    let legacy_key = (PROPOSAL_COUNT, proposal_id);
+    let legacy: StreamProposalV1 = env.storage().instance().get(&legacy_key)?;
+    env.storage().instance().remove(&legacy_key);
+    if legacy.executed {
+        return None;
+    }
+
+    // The previous version let anyone approve, had no rejection vote and
+    // pulled the funds on execution
+    let proposal = StreamProposal {
+        sender: legacy.sender,
+        receiver: legacy.receiver,
+        token: legacy.token,
+        total_amount: legacy.total_amount,
+        start_time: legacy.start_time,
+        end_time: legacy.end_time,
+        options: StreamOptions::new(env),
+        approval_weight: legacy.approvers.len(),
+        approvers: legacy.approvers,
+        required_approvals: legacy.required_approvals,
+        rejectors: Vec::new(env),
+        required_rejections: 0,
+        rejection_weight: 0,
+        voters: Vec::new(env),
+        council_id: None,
+        deadline: legacy.deadline,
+        timelock: 0,
+        executable_at: None,
+        escrowed: false,
+    };
+    save_proposal(env, proposal_id, &proposal);
+    Some(proposal)
+}
+
+pub fn load_council(env: &Env, council_id: u64) -> Option<Council> {
+    let key = DataKey::Council(council_id);
+    let council = env.storage().persistent().get::<_, Council>(&key)?;
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    Some(council)
+}
+
+pub fn save_council(env: &Env, council_id: u64, council: &Council) {
+    let key = DataKey::Council(council_id);
+    env.storage().persistent().set(&key, council);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn load_council_update(env: &Env, council_id: u64) -> Option<CouncilUpdate> {
+    let key = DataKey::CouncilUpdate(council_id);
+    let update = env.storage().persistent().get::<_, CouncilUpdate>(&key)?;
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    Some(update)
+}
+
+pub fn save_council_update(env: &Env, council_id: u64, update: &CouncilUpdate) {
+    let key = DataKey::CouncilUpdate(council_id);
+    env.storage().persistent().set(&key, update);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn remove_council_update(env: &Env, council_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::CouncilUpdate(council_id));
+}
+
+pub fn load_receipt(env: &Env, stream_id: u64) -> Option<StreamReceipt> {
+    let key = DataKey::Receipt(stream_id);
+    match env.storage().persistent().get::<_, StreamReceipt>(&key) {
+        Some(receipt) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+            Some(receipt)
+        }
+        None => {
+            migrate_legacy_stream(env, stream_id)?;
+            env.storage().persistent().get(&key)
+        }
+    }
+}
+
+pub fn save_receipt(env: &Env, stream_id: u64, receipt: &StreamReceipt) {
+    let key = DataKey::Receipt(stream_id);
+    env.storage().persistent().set(&key, receipt);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Move a stream and its receipt from the contract instance into their own
+/// persistent entries. Returns `None` when there is no legacy entry for the id.
+pub fn migrate_legacy_stream(env: &Env, stream_id: u64) -> Option<Stream> {
+    if env
+        .storage()
+        .instance()
+        .has(&DataKey::MigrationExecuted(STREAM_STORAGE_MIGRATION))
+    {
+        return None;
+    }
+
+    let legacy_key = (STREAM_COUNT, stream_id);
+    let legacy: StreamV1 = env.storage().instance().get(&legacy_key)?;
+    let stream = Stream {
+        sender: legacy.sender,
+        receiver: legacy.receiver.clone(),
+        token: legacy.token,
+        total_amount: legacy.total_amount,
+        start_time: legacy.start_time,
+        cliff_time: legacy.start_time,
+        end_time: legacy.end_time,
+        withdrawn: legacy.withdrawn,
+        withdrawn_amount: legacy.withdrawn_amount,
+        withdrawal_fee_bps: 0,
+        cancelled: legacy.cancelled,
+        // The previous version redirected streams by changing `receiver`
+        // alone, leaving `receipt_owner` on the original receiver. Receipts
+        // could not be traded then, so the claim belongs to `receiver`.
+        receipt_owner: legacy.receiver,
+        is_paused: legacy.is_paused,
+        paused_time: legacy.paused_time,
+        total_paused_duration: legacy.total_paused_duration,
+        milestones: legacy.milestones,
+        curve_type: match legacy.curve_type {
+            1 => CurveType::Exponential,
+            _ => CurveType::Linear,
+        },
+        interest_strategy: legacy.interest_strategy,
+        vault_address: legacy.vault_address,
+        deposited_principal: legacy.deposited_principal,
+        metadata: legacy.metadata,
+        is_usd_pegged: legacy.is_usd_pegged,
+        usd_amount: legacy.usd_amount,
+        oracle_address: legacy.oracle_address,
+        oracle_max_staleness: legacy.oracle_max_staleness,
+        price_min: legacy.price_min,
+        price_max: legacy.price_max,
+        is_soulbound: legacy.is_soulbound,
+        clawback_enabled: legacy.clawback_enabled,
+        arbiter: legacy.arbiter,
+        is_frozen: legacy.is_frozen,
+        cancellation: CancellationPolicy {
+            authority: CancelAuthority::Both,
+            cancelable_until: None,
+        },
+        initial_unlock_bps: 0,
+        rate: StreamRate::Scheduled,
+        carried_amount: 0,
+    };
+    save_stream(env, stream_id, &stream);
+    env.storage().instance().remove(&legacy_key);
+    index::add(env, StreamIndex::Sender, &stream.sender, stream_id);
+    index::add(env, StreamIndex::Receiver, &stream.receipt_owner, stream_id);
+
+    // Re-mint the receipt to the stream's holder, keeping its mint time
+    let legacy_receipt_key = (RECEIPT, stream_id);
+    let minted_at = env
+        .storage()
+        .instance()
+        .get::<_, StreamReceipt>(&legacy_receipt_key)
+        .map_or(env.ledger().timestamp(), |receipt| receipt.minted_at);
+    save_receipt(
+        env,
+        stream_id,
+        &StreamReceipt {
+            stream_id,
+            owner: stream.receipt_owner.clone(),
+            minted_at,
+        },
+    );
+    env.storage().instance().remove(&legacy_receipt_key);
+
+    Some(stream)
+}
@@ -0,0 +1,279 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::storage::{PROPOSAL_COUNT, RECEIPT, STREAM_COUNT};
+use crate::test_utils::create_token_contract;
+use crate::types::{CurveType, DataKey, StreamProposalV1, StreamReceipt, StreamV1};
+use crate::{StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    Address, Env, Vec,
+};
+
+fn legacy_stream(env: &Env, receiver: &Address, token: &Address, curve_type: u32) -> StreamV1 {
+    let sender = Address::generate(env);
+    StreamV1 {
+        sender: sender.clone(),
+        receiver: receiver.clone(),
+        token: token.clone(),
+        total_amount: 1000,
+        start_time: 0,
+        end_time: 100,
+        withdrawn: 0,
+        withdrawn_amount: 100,
+        cancelled: false,
+        receipt_owner: receiver.clone(),
+        is_paused: false,
+        paused_time: 0,
+        total_paused_duration: 0,
+        milestones: Vec::new(env),
+        curve_type,
+        interest_strategy: 0,
+        vault_address: None,
+        deposited_principal: 1000,
+        metadata: None,
+        is_usd_pegged: false,
+        usd_amount: 0,
+        oracle_address: sender,
+        oracle_max_staleness: 0,
+        price_min: 0,
+        price_max: 0,
+        is_soulbound: false,
+        clawback_enabled: false,
+        arbiter: None,
+        is_frozen: false,
+    }
+}
+
+/// Deploy the contract and write `count` streams the way the previous
+/// version stored them: inside the contract instance
+fn setup_legacy_streams(
+    env: &Env,
+    count: u64,
+) -> (StellarStreamContractClient<'_>, Address, Address, Address) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 50);
+
+    let admin = Address::generate(env);
+    let receiver = Address::generate(env);
+    let (token, _) = create_token_contract(env, &admin);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+    client.initialize(&admin);
+    StellarAssetClient::new(env, &token).mint(&contract_id, &(900 * count as i128));
+
+    env.as_contract(&contract_id, || {
+        for stream_id in 0..count {
+            let stream = legacy_stream(env, &receiver, &token, (stream_id % 2) as u32);
+            env.storage()
+                .instance()
+                .set(&(STREAM_COUNT, stream_id), &stream);
+            env.storage().instance().set(
+                &(RECEIPT, stream_id),
+                &StreamReceipt {
+                    stream_id,
+                    owner: receiver.clone(),
+                    minted_at: 0,
+                },
+            );
+        }
+        env.storage().instance().set(&STREAM_COUNT, &count);
+    });
+
+    (client, admin, receiver, token)
+}
+
+#[test]
+fn test_migrate_stream_storage_in_batches() {
+    let env = Env::default();
+    let (client, admin, receiver, token) = setup_legacy_streams(&env, 3);
+
+    assert!(!client.migrate_stream_storage(&admin, &2));
+    assert!(client.migrate_stream_storage(&admin, &2));
+
+    for stream_id in 0..3 {
+        let stream = client.get_stream(&stream_id);
+        assert_eq!(stream.receiver, receiver);
+        assert_eq!(stream.token, token);
+        assert_eq!(stream.cliff_time, stream.start_time);
+        assert_eq!(stream.withdrawn_amount, 100);
+        assert_eq!(stream.withdrawal_fee_bps, 0);
+        assert_eq!(client.get_receipt(&stream_id).owner, receiver);
+    }
+    assert_eq!(client.get_stream(&1).curve_type, CurveType::Exponential);
+    assert_eq!(client.get_receiver_stream_count(&receiver), 3);
+
+    env.as_contract(&client.address, || {
+        for stream_id in 0..3u64 {
+            assert!(!env.storage().instance().has(&(STREAM_COUNT, stream_id)));
+            assert!(!env.storage().instance().has(&(RECEIPT, stream_id)));
+            assert!(env.storage().persistent().has(&DataKey::Stream(stream_id)));
+        }
+    });
+
+    assert_eq!(
+        client.try_migrate_stream_storage(&admin, &2),
+        Err(Ok(Error::AlreadyExecuted))
+    );
+}
+
+#[test]
+fn test_legacy_stream_migrated_on_access() {
+    let env = Env::default();
+    let (client, _, receiver, _) = setup_legacy_streams(&env, 1);
+
+    // 500 unlocked at t=50, 100 of it already withdrawn before the upgrade
+    assert_eq!(client.withdraw(&0, &receiver), 400);
+
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().instance().has(&(STREAM_COUNT, 0u64)));
+        assert!(env.storage().persistent().has(&DataKey::Receipt(0)));
+    });
+}
+
+#[test]
+fn test_redirected_legacy_stream_belongs_to_new_receiver() {
+    let env = Env::default();
+    let (client, _, original, token) = setup_legacy_streams(&env, 0);
+    StellarAssetClient::new(&env, &token).mint(&client.address, &900);
+
+    // The previous `transfer_receiver` moved `receiver` but left the receipt
+    // and `receipt_owner` with the original receiver
+    let redirected = Address::generate(&env);
+    let legacy = StreamV1 {
+        receiver: redirected.clone(),
+        ..legacy_stream(&env, &original, &token, 0)
+    };
+    let sender = legacy.sender.clone();
+    env.as_contract(&client.address, || {
+        let storage = env.storage().instance();
+        storage.set(&(STREAM_COUNT, 0u64), &legacy);
+        storage.set(
+            &(RECEIPT, 0u64),
+            &StreamReceipt {
+                stream_id: 0,
+                owner: original.clone(),
+                minted_at: 7,
+            },
+        );
+        storage.set(&STREAM_COUNT, &1u64);
+    });
+
+    let stream = client.get_stream(&0);
+    assert_eq!(stream.receipt_owner, redirected);
+    let receipt = client.get_receipt(&0);
+    assert_eq!(receipt.owner, redirected);
+    assert_eq!(receipt.minted_at, 7);
+    assert_eq!(client.get_receiver_stream_count(&redirected), 1);
+    assert_eq!(client.get_receiver_stream_count(&original), 0);
+
+    assert_eq!(
+        client.try_withdraw(&0, &original),
+        Err(Ok(Error::NotReceiptOwner))
+    );
+    assert_eq!(client.withdraw(&0, &redirected), 400);
+
+    // The sender can still redirect, and the receipt moves with the index
+    let next = Address::generate(&env);
+    client.transfer_receiver(&0, &sender, &next);
+    assert_eq!(client.get_receiver_stream_count(&redirected), 0);
+    assert_eq!(client.get_receiver_stream_count(&next), 1);
+}
+
+#[test]
+fn test_legacy_proposals_migrated_on_access() {
+    let env = Env::default();
+    let (client, _, receiver, token) = setup_legacy_streams(&env, 0);
+    env.mock_all_auths_allowing_non_root_auth();
+    let sender = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&sender, &1000);
+
+    let legacy_proposal = |executed: bool| StreamProposalV1 {
+        sender: sender.clone(),
+        receiver: receiver.clone(),
+        token: token.clone(),
+        total_amount: 1000,
+        start_time: 100,
+        end_time: 200,
+        approvers: Vec::from_array(&env, [Address::generate(&env)]),
+        required_approvals: 2,
+        deadline: 1000,
+        executed,
+    };
+    env.as_contract(&client.address, || {
+        let storage = env.storage().instance();
+        storage.set(&(PROPOSAL_COUNT, 0u64), &legacy_proposal(false));
+        storage.set(&(PROPOSAL_COUNT, 1u64), &legacy_proposal(true));
+        storage.set(&PROPOSAL_COUNT, &2u64);
+    });
+
+    let proposal = client.get_proposal(&0);
+    assert_eq!(proposal.approvers.len(), 1);
+    assert_eq!(proposal.approval_weight, 1);
+    assert_eq!(proposal.required_approvals, 2);
+    assert_eq!(proposal.required_rejections, 0);
+    assert!(!proposal.escrowed);
+
+    // Executed proposals are no longer kept; pruning clears their entries
+    assert_eq!(
+        client.try_get_proposal(&1),
+        Err(Ok(Error::ProposalNotFound))
+    );
+    assert_eq!(
+        client.prune_expired_proposals(&Vec::from_array(&env, [1])),
+        0
+    );
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().instance().has(&(PROPOSAL_COUNT, 0u64)));
+        assert!(!env.storage().instance().has(&(PROPOSAL_COUNT, 1u64)));
+        assert!(!env.storage().instance().has(&DataKey::Proposal(0)));
+        assert!(env.storage().persistent().has(&DataKey::Proposal(0)));
+    });
+
+    // The migrated proposal still executes with its recorded approval
+    client.approve_proposal(&0, &Address::generate(&env));
+    assert_eq!(client.get_stream(&0).total_amount, 1000);
+    assert_eq!(
+        client.create_proposal(&sender, &receiver, &token, &1, &100, &200, &1, &1000),
+        2
+    );
+}
+
+#[test]
+fn test_streams_created_mid_migration_are_kept() {
+    let env = Env::default();
+    let (client, admin, receiver, token) = setup_legacy_streams(&env, 2);
+
+    assert!(!client.migrate_stream_storage(&admin, &1));
+
+    let sender = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&sender, &1000);
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token,
+        &1000,
+        &50,
+        &150,
+        &CurveType::Linear,
+        &false,
+    );
+    assert_eq!(stream_id, 2);
+
+    assert!(client.migrate_stream_storage(&admin, &10));
+    assert_eq!(client.get_stream(&1).withdrawn_amount, 100);
+    assert_eq!(client.get_stream(&2).sender, sender);
+}
+
+#[test]
+fn test_only_admin_can_migrate_storage() {
+    let env = Env::default();
+    let (client, _, _, _) = setup_legacy_streams(&env, 1);
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_migrate_stream_storage(&outsider, &10),
+        Err(Ok(Error::Unauthorized))
+    );
+}
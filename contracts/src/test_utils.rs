@@ -0,0 +1,56 @@
+#![cfg(test)]
+//! Fixtures shared by the per-feature `*_test.rs` modules.
+use crate::{StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+pub fn create_token_contract<'a>(env: &Env, admin: &Address) -> (Address, TokenClient<'a>) {
+    let contract_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    (contract_id.clone(), TokenClient::new(env, &contract_id))
+}
+
+/// Registers the contract and a token, minting `balance` to the sender.
+/// Returns `(client, sender, receiver, token_address, token_client)`.
+pub fn setup_with_balance(
+    env: &Env,
+    balance: i128,
+) -> (
+    StellarStreamContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    TokenClient<'_>,
+) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+    let receiver = Address::generate(env);
+
+    let (token_address, token_client) = create_token_contract(env, &admin);
+    StellarAssetClient::new(env, &token_address).mint(&sender, &balance);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+
+    (client, sender, receiver, token_address, token_client)
+}
+
+/// [`setup_with_balance`] with the usual 10 000 token float.
+pub fn setup_test(
+    env: &Env,
+) -> (
+    StellarStreamContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    TokenClient<'_>,
+) {
+    setup_with_balance(env, 10_000)
+}
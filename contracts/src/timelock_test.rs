@@ -0,0 +1,282 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::create_token_contract;
+use crate::types::{
+    CouncilMember, ProposalConfig, ProposalQueuedEvent, StreamOptions, PROPOSAL_EXECUTION_WINDOW,
+};
+use crate::{StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, IntoVal, Vec,
+};
+
+struct Setup<'a> {
+    client: StellarStreamContractClient<'a>,
+    token_client: TokenClient<'a>,
+    admin: Address,
+    sender: Address,
+    receiver: Address,
+    token: Address,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().with_mut(|li| li.timestamp = 50);
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+    let receiver = Address::generate(env);
+
+    let (token, token_client) = create_token_contract(env, &admin);
+    StellarAssetClient::new(env, &token).mint(&sender, &10_000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+    client.initialize(&admin);
+
+    Setup {
+        client,
+        token_client,
+        admin,
+        sender,
+        receiver,
+        token,
+    }
+}
+
+fn config(env: &Env, timelock: Option<u64>) -> ProposalConfig {
+    ProposalConfig {
+        required_approvals: 1,
+        required_rejections: 0,
+        voters: Vec::new(env),
+        deadline: 1000,
+        escrow: true,
+        council_id: None,
+        timelock,
+    }
+}
+
+fn propose(env: &Env, s: &Setup, config: &ProposalConfig) -> u64 {
+    s.client.create_proposal_with_config(
+        &s.sender,
+        &s.receiver,
+        &s.token,
+        &1000,
+        &100,
+        &2000,
+        &StreamOptions::new(env),
+        config,
+    )
+}
+
+#[test]
+fn test_quorum_queues_proposal_until_timelock_passes() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let proposal_id = propose(&env, &s, &config(&env, Some(300)));
+    s.client
+        .approve_proposal(&proposal_id, &Address::generate(&env));
+
+    let queued = env
+        .events()
+        .all()
+        .iter()
+        .find(|event| event.1 == (symbol_short!("queue"), proposal_id).into_val(&env))
+        .unwrap();
+    let queued: ProposalQueuedEvent = queued.2.into_val(&env);
+    assert_eq!(queued.executable_at, 350);
+
+    let proposal = s.client.get_proposal(&proposal_id);
+    assert_eq!(proposal.executable_at, Some(350));
+
+    // Voting is closed while queued
+    let result = s
+        .client
+        .try_approve_proposal(&proposal_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::ProposalQueued)));
+    let result = s
+        .client
+        .try_reject_proposal(&proposal_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::ProposalQueued)));
+
+    env.ledger().with_mut(|li| li.timestamp = 349);
+    let result = s.client.try_execute_queued_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::TimelockActive)));
+
+    env.ledger().with_mut(|li| li.timestamp = 350);
+    let stream_id = s.client.execute_queued_proposal(&proposal_id);
+    assert_eq!(s.client.get_stream(&stream_id).total_amount, 1000);
+
+    let result = s.client.try_execute_queued_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+}
+
+#[test]
+fn test_unqueued_proposal_cannot_be_executed_or_vetoed() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let proposal_id = propose(&env, &s, &config(&env, Some(300)));
+    let result = s.client.try_execute_queued_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalNotQueued)));
+    let result = s.client.try_veto_proposal(&proposal_id, &s.sender);
+    assert_eq!(result, Err(Ok(Error::ProposalNotQueued)));
+}
+
+#[test]
+fn test_sender_vetoes_queued_proposal() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let proposal_id = propose(&env, &s, &config(&env, Some(300)));
+    s.client
+        .approve_proposal(&proposal_id, &Address::generate(&env));
+    assert_eq!(s.token_client.balance(&s.sender), 9000);
+
+    let result = s.client.try_veto_proposal(&proposal_id, &s.receiver);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    s.client.veto_proposal(&proposal_id, &s.sender);
+    assert_eq!(s.token_client.balance(&s.sender), 10_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    let result = s.client.try_execute_queued_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+}
+
+#[test]
+fn test_council_member_vetoes_queued_proposal() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let (member1, member2) = (Address::generate(&env), Address::generate(&env));
+
+    let members = vec![
+        &env,
+        CouncilMember {
+            member: member1.clone(),
+            weight: 2,
+        },
+        CouncilMember {
+            member: member2.clone(),
+            weight: 1,
+        },
+    ];
+    let council_id = s.client.create_council(&s.sender, &members, &2);
+    let proposal_id = propose(
+        &env,
+        &s,
+        &ProposalConfig {
+            council_id: Some(council_id),
+            ..config(&env, Some(300))
+        },
+    );
+    s.client.approve_proposal(&proposal_id, &member1);
+
+    // Outvoted members can still stop the proposal during the delay
+    s.client.veto_proposal(&proposal_id, &member2);
+    assert_eq!(s.token_client.balance(&s.sender), 10_000);
+}
+
+#[test]
+fn test_global_timelock_applies_by_default() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let result = s.client.try_set_proposal_timelock(&s.sender, &600);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    s.client.set_proposal_timelock(&s.admin, &600);
+
+    let proposal_id = s.client.create_proposal(
+        &s.sender,
+        &s.receiver,
+        &s.token,
+        &1000,
+        &100,
+        &2000,
+        &1,
+        &1000,
+    );
+    assert_eq!(s.client.get_proposal(&proposal_id).timelock, 600);
+    s.client
+        .approve_proposal(&proposal_id, &Address::generate(&env));
+    assert_eq!(s.client.get_proposal(&proposal_id).executable_at, Some(650));
+
+    // The default timelock makes plain proposals escrow their funds
+    assert!(s.client.get_proposal(&proposal_id).escrowed);
+    assert_eq!(s.token_client.balance(&s.sender), 9000);
+
+    // A proposal can opt out with its own zero delay
+    let proposal_id = propose(&env, &s, &config(&env, Some(0)));
+    s.client
+        .approve_proposal(&proposal_id, &Address::generate(&env));
+    let result = s.client.try_get_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+}
+
+#[test]
+fn test_queued_proposal_survives_deadline() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let proposal_id = propose(&env, &s, &config(&env, Some(2000)));
+    s.client
+        .approve_proposal(&proposal_id, &Address::generate(&env));
+
+    env.ledger().with_mut(|li| li.timestamp = 1500);
+    let pruned = s.client.prune_expired_proposals(&vec![&env, proposal_id]);
+    assert_eq!(pruned, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 2050);
+    s.client.execute_queued_proposal(&proposal_id);
+    let result = s.client.try_get_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+}
+
+#[test]
+fn test_timelock_requires_escrow() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let unescrowed = ProposalConfig {
+        escrow: false,
+        ..config(&env, Some(300))
+    };
+    let result = s.client.try_create_proposal_with_config(
+        &s.sender,
+        &s.receiver,
+        &s.token,
+        &1000,
+        &100,
+        &2000,
+        &StreamOptions::new(&env),
+        &unescrowed,
+    );
+    assert_eq!(result, Err(Ok(Error::EscrowRequired)));
+}
+
+#[test]
+fn test_queued_proposal_expires_after_execution_window() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let proposal_id = propose(&env, &s, &config(&env, Some(300)));
+    s.client
+        .approve_proposal(&proposal_id, &Address::generate(&env));
+    assert_eq!(s.token_client.balance(&s.sender), 9000);
+
+    let expires_at = 350 + PROPOSAL_EXECUTION_WINDOW;
+    env.ledger().with_mut(|li| li.timestamp = expires_at);
+    let pruned = s.client.prune_expired_proposals(&vec![&env, proposal_id]);
+    assert_eq!(pruned, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = expires_at + 1);
+    let result = s.client.try_execute_queued_proposal(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalExpired)));
+
+    let pruned = s.client.prune_expired_proposals(&vec![&env, proposal_id]);
+    assert_eq!(pruned, 1);
+    assert_eq!(s.token_client.balance(&s.sender), 10_000);
+}
@@ -1,18 +1,14 @@
 #![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::create_token_contract;
+use crate::types::{CurveType, StreamToppedUpEvent, TopUpMode};
 use crate::{StellarStreamContract, StellarStreamContractClient};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger, LedgerInfo},
-    token::{StellarAssetClient, TokenClient},
-    Address, Env, Vec,
+    testutils::{Address as _, Events, Ledger},
+    token::StellarAssetClient,
+    Address, Env, IntoVal, Vec,
 };
 
-fn create_token_contract<'a>(env: &Env, admin: &Address) -> (Address, TokenClient<'a>) {
-    let contract_id = env
-        .register_stellar_asset_contract_v2(admin.clone())
-        .address();
-    (contract_id.clone(), TokenClient::new(env, &contract_id))
-}
-
 #[test]
 fn test_top_up_extends_duration() {
     let env = Env::default();
@@ -48,7 +44,7 @@ fn test_top_up_extends_duration() {
     assert_eq!(stream_before.total_amount, 1000);
     assert_eq!(stream_before.end_time, 200);
 
-    client.top_up_stream(&stream_id, &sender, &1000);
+    client.top_up_stream(&stream_id, &sender, &1000, &TopUpMode::ExtendDuration);
 
     let stream_after = client.get_stream(&stream_id);
     assert_eq!(stream_after.total_amount, 2000);
@@ -89,7 +85,7 @@ fn test_only_sender_can_top_up() {
         &None,
     );
 
-    client.top_up_stream(&stream_id, &other, &500);
+    client.top_up_stream(&stream_id, &other, &500, &TopUpMode::ExtendDuration);
 }
 
 #[test]
@@ -125,7 +121,7 @@ fn test_cannot_top_up_cancelled_stream() {
     );
 
     client.cancel(&stream_id, &sender);
-    client.top_up_stream(&stream_id, &sender, &1000);
+    client.top_up_stream(&stream_id, &sender, &1000, &TopUpMode::ExtendDuration);
 }
 
 #[test]
@@ -159,10 +155,195 @@ fn test_top_up_multiple_times() {
         &None,
     );
 
-    client.top_up_stream(&stream_id, &sender, &1000);
-    client.top_up_stream(&stream_id, &sender, &1000);
+    client.top_up_stream(&stream_id, &sender, &1000, &TopUpMode::ExtendDuration);
+    client.top_up_stream(&stream_id, &sender, &1000, &TopUpMode::ExtendDuration);
 
     let stream = client.get_stream(&stream_id);
     assert_eq!(stream.total_amount, 3000);
     assert_eq!(stream.end_time, 400);
 }
+
+#[test]
+fn test_top_up_small_stream() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let receiver = Address::generate(&env);
+
+    let (token_address, _token_client) = create_token_contract(&env, &admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_address);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+
+    token_admin_client.mint(&sender, &100);
+
+    // Less than one token per second
+    let milestones = Vec::new(&env);
+    let stream_id = client.create_stream_with_milestones(
+        &sender,
+        &receiver,
+        &token_address,
+        &50,
+        &0,
+        &100,
+        &milestones,
+        &crate::types::CurveType::Linear,
+        &false,
+        &None,
+    );
+
+    client.top_up_stream(&stream_id, &sender, &50, &TopUpMode::ExtendDuration);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.total_amount, 100);
+    assert_eq!(stream.end_time, 200);
+}
+
+/// 1000 over 0..100 on `curve`, with funds left for top-ups
+fn setup_stream(
+    env: &Env,
+    curve: CurveType,
+) -> (StellarStreamContractClient<'_>, Address, Address, u64) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+    let receiver = Address::generate(env);
+
+    let (token_address, _token_client) = create_token_contract(env, &admin);
+    StellarAssetClient::new(env, &token_address).mint(&sender, &10_000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_address,
+        &1000,
+        &0,
+        &100,
+        &curve,
+        &false,
+    );
+
+    (client, sender, receiver, stream_id)
+}
+
+#[test]
+fn test_increase_rate_spreads_top_up_over_remaining_period() {
+    let env = Env::default();
+    let (client, sender, receiver, stream_id) = setup_stream(&env, CurveType::Linear);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    client.top_up_stream(&stream_id, &sender, &1000, &TopUpMode::IncreaseRate);
+    assert_eq!(client.get_stream(&stream_id).end_time, 100);
+
+    // Nothing of the top-up is released at once
+    assert_eq!(client.withdraw(&stream_id, &receiver), 500);
+
+    env.ledger().with_mut(|li| li.timestamp = 75);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 750);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 750);
+}
+
+#[test]
+fn test_extend_duration_never_lowers_unlocked_amount() {
+    let env = Env::default();
+    let (client, sender, receiver, stream_id) = setup_stream(&env, CurveType::Exponential);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 250);
+
+    // Doubling the duration would drop the exponential curve to 2000 * 1/16;
+    // the end only moves as far as keeps at least 250 unlocked
+    client.top_up_stream(&stream_id, &sender, &1000, &TopUpMode::ExtendDuration);
+    assert_eq!(client.get_stream(&stream_id).end_time, 141);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 1);
+}
+
+#[test]
+fn test_increase_rate_restarts_exponential_curve() {
+    let env = Env::default();
+    let (client, sender, receiver, stream_id) = setup_stream(&env, CurveType::Exponential);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 250);
+
+    // The remaining 1750 runs a fresh quadratic over 50..100 rather than the
+    // rest of the original one: 1750 * (25 / 50)^2 at the halfway point
+    client.top_up_stream(&stream_id, &sender, &1000, &TopUpMode::IncreaseRate);
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.start_time, 50);
+    assert_eq!(stream.carried_amount, 250);
+
+    env.ledger().with_mut(|li| li.timestamp = 75);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 437);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 2000 - 250 - 437);
+}
+
+#[test]
+fn test_top_up_ended_stream_starts_new_tranche() {
+    let env = Env::default();
+    let (client, sender, receiver, stream_id) = setup_stream(&env, CurveType::Linear);
+
+    env.ledger().with_mut(|li| li.timestamp = 150);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 1000);
+
+    // Same pace as before: 500 takes 50 seconds
+    client.top_up_stream(&stream_id, &sender, &500, &TopUpMode::ExtendDuration);
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.start_time, 150);
+    assert_eq!(stream.end_time, 200);
+    assert_eq!(stream.carried_amount, 1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 175);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 250);
+
+    // Over the length of the last schedule instead, whatever the amount
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.top_up_stream(&stream_id, &sender, &1000, &TopUpMode::IncreaseRate);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 250);
+    assert_eq!(client.get_stream(&stream_id).end_time, 250);
+
+    env.ledger().with_mut(|li| li.timestamp = 225);
+    assert_eq!(client.withdraw(&stream_id, &receiver), 500);
+}
+
+#[test]
+fn test_top_up_event_records_mode() {
+    let env = Env::default();
+    let (client, sender, _, stream_id) = setup_stream(&env, CurveType::Linear);
+
+    client.top_up_stream(&stream_id, &sender, &500, &TopUpMode::IncreaseRate);
+
+    let (_, _, data) = env.events().all().last().unwrap();
+    let event: StreamToppedUpEvent = data.into_val(&env);
+    assert_eq!(event.mode, TopUpMode::IncreaseRate);
+    assert_eq!(event.new_total, 1500);
+    assert_eq!(event.new_end_time, 100);
+}
+
+#[test]
+fn test_increase_rate_rejected_while_paused() {
+    let env = Env::default();
+    let (client, sender, _, stream_id) = setup_stream(&env, CurveType::Linear);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    client.pause_stream(&stream_id, &sender);
+    assert_eq!(
+        client.try_top_up_stream(&stream_id, &sender, &500, &TopUpMode::IncreaseRate),
+        Err(Ok(Error::StreamPaused))
+    );
+
+    // Extending only pushes the end out
+    client.top_up_stream(&stream_id, &sender, &500, &TopUpMode::ExtendDuration);
+    assert_eq!(client.get_stream(&stream_id).end_time, 150);
+}
@@ -9,7 +9,6 @@ struct TtlTestContext {
     env: Env,
     contract_id: Address,
     client: StellarStreamContractClient<'static>,
-    token_admin: Address,
     token: token::StellarAssetClient<'static>,
     token_id: Address,
 }
@@ -42,7 +41,6 @@ fn setup_ttl_test() -> TtlTestContext {
         env,
         contract_id,
         client,
-        token_admin,
         token,
         token_id,
     }
@@ -389,9 +387,7 @@ fn test_paused_stream_ttl_survival() {
 }
 
 /// Test TTL survival with stream cancellation and recreation
-/// Note: This test is currently disabled due to token balance issues in the cancel operation
 #[test]
-#[ignore]
 fn test_cancelled_stream_ttl_and_recreation() {
     let ctx = setup_ttl_test();
     let sender = Address::generate(&ctx.env);
@@ -472,3 +468,76 @@ fn test_cancelled_stream_ttl_and_recreation() {
         "New stream should be completed, not cancelled"
     );
 }
+
+/// Test that many streams each live in their own persistent entry, stay
+/// readable for years and keep the contract instance small. The instance
+/// holds no per-stream entry, so its size does not depend on the count.
+#[test]
+fn test_many_streams_ttl_survival() {
+    let ctx = setup_ttl_test();
+    let sender = Address::generate(&ctx.env);
+    let receiver = Address::generate(&ctx.env);
+
+    let stream_count = 200_u64;
+    let amount_per_stream = 1_000_i128;
+    let start_time = 1000;
+    let end_time = start_time + (SECONDS_PER_YEAR * 4);
+    ctx.token
+        .mint(&sender, &(amount_per_stream * stream_count as i128));
+
+    for _ in 0..stream_count {
+        ctx.client.create_stream(
+            &sender,
+            &receiver,
+            &ctx.token_id,
+            &amount_per_stream,
+            &start_time,
+            &end_time,
+            &CurveType::Linear,
+            &false,
+        );
+    }
+
+    ctx.env.as_contract(&ctx.contract_id, || {
+        use soroban_sdk::testutils::storage::Persistent as _;
+
+        for stream_id in [0, stream_count / 2, stream_count - 1] {
+            let key = DataKey::Stream(stream_id);
+            assert!(ctx.env.storage().persistent().has(&key));
+            assert!(ctx
+                .env
+                .storage()
+                .persistent()
+                .has(&DataKey::Receipt(stream_id)));
+            assert!(!ctx
+                .env
+                .storage()
+                .instance()
+                .has(&(storage::STREAM_COUNT, stream_id)));
+            assert!(ctx.env.storage().persistent().get_ttl(&key) >= storage::TTL_THRESHOLD);
+        }
+    });
+
+    // Jump 2 years: every stream is still live and withdrawable
+    advance_time_and_extend_ttl(&ctx.env, SECONDS_PER_YEAR * 2, start_time);
+
+    let mut total_withdrawn = 0;
+    for stream_id in (0..stream_count).step_by(10) {
+        let stream = ctx.client.get_stream(&stream_id);
+        assert_eq!(stream.total_amount, amount_per_stream);
+        total_withdrawn += ctx.client.withdraw(&stream_id, &receiver);
+    }
+    assert_eq!(total_withdrawn, amount_per_stream / 2 * 20);
+
+    // Reading a stream extends its TTL back to the full window
+    ctx.env.as_contract(&ctx.contract_id, || {
+        use soroban_sdk::testutils::storage::Persistent as _;
+
+        let ttl = ctx
+            .env
+            .storage()
+            .persistent()
+            .get_ttl(&DataKey::Stream(stream_count - 10));
+        assert!(ttl >= storage::TTL_THRESHOLD);
+    });
+}
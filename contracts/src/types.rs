@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, BytesN, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
 
 // Interest distribution strategies
 // Bits can be combined: e.g., 0b011 = 50% sender, 50% receiver
@@ -15,12 +15,155 @@ pub const INTEREST_SPLIT_SENDER_RECEIVER: u32 = 0b011; // 3: 50/50 sender/receiv
 #[allow(dead_code)]
 pub const INTEREST_SPLIT_ALL: u32 = 0b111; // 7: 33/33/33 split
 
+// Protocol fees are capped at 10% (fee_bps uses 10_000 as denominator)
+pub const MAX_FEE_BPS: u32 = 1_000;
+
+// Custom curves are capped to keep unlock calculation cheap
+pub const MAX_CURVE_POINTS: u32 = 32;
+
+// Power curve exponents are fixed-point with three decimals (1_000 = 1.0),
+// from 0.1 (front-loaded) to 10.0 (back-loaded)
+pub const POWER_EXPONENT_SCALE: u32 = 1_000;
+pub const MIN_POWER_EXPONENT: u32 = 100;
+pub const MAX_POWER_EXPONENT: u32 = 10_000;
+
+/// Breakpoint of a custom vesting curve: `unlocked_bps` of the curve amount
+/// has unlocked `time_offset` seconds after `start_time`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CurvePoint {
+    pub time_offset: u64,
+    pub unlocked_bps: u32,
+}
+
+/// `tranches` equal amounts released every `period_secs`. The periods must
+/// add up to exactly the stream's duration.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeriodicSchedule {
+    pub period_secs: u64,
+    pub tranches: u32,
+}
+
 // Curve types for vesting schedules
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CurveType {
-    Linear = 0,
-    Exponential = 1,
+    Linear,
+    Exponential,
+    /// Piecewise-linear through the breakpoints, starting from (0, 0). The
+    /// last point must be (end_time - start_time, 10_000).
+    Custom(Vec<CurvePoint>),
+    /// Step schedule: nothing between tranches, the last one absorbs rounding dust
+    Periodic(PeriodicSchedule),
+    /// `unlocked = total * progress^exponent`, exponent scaled by
+    /// `POWER_EXPONENT_SCALE`: 500 front-loads, 3_000 back-loads
+    Power(u32),
+}
+
+/// Accrual state of an open-ended stream. `accrued` was locked in at
+/// `checkpoint_time`; from there `rate_per_second` accrues every second.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateSchedule {
+    pub rate_per_second: i128,
+    pub checkpoint_time: u64,
+    /// Streamed up to `checkpoint_time`. May exceed the stream's
+    /// `total_amount`; the excess is debt owed to the receiver.
+    pub accrued: i128,
+    /// Set once the stream has been seen owing more than its balance,
+    /// cleared when a deposit covers the debt again
+    pub insolvent: bool,
+}
+
+/// How a stream's unlocked amount accrues
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StreamRate {
+    /// Follows the curve between `start_time` and `end_time`
+    Scheduled,
+    /// Open-ended; accrues at a per-second rate, running into debt once the
+    /// balance runs out
+    PerSecond(RateSchedule),
+    /// Open-ended; accrues at a per-second rate drawn from the sender's
+    /// shared deposit for the token, and stops when that deposit runs out
+    FromDeposit(RateSchedule),
+}
+
+/// How `top_up_stream` fits new funds into a stream's schedule
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TopUpMode {
+    /// Keep the stream's pace and push its end out
+    ExtendDuration,
+    /// Keep the end and release the funds faster over the remaining period
+    IncreaseRate,
+}
+
+/// New terms for a stream, agreed by both sender and receiver. The
+/// schedule runs from the proposal to `end_time`; custom breakpoints and
+/// periodic tranches are laid out over that window and stretched onto what
+/// is left of it when the amendment is accepted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AmendmentTerms {
+    pub total_amount: i128,
+    pub end_time: u64,
+    pub cliff_time: u64,
+    pub curve_type: CurveType,
+    pub receiver: Address,
+}
+
+/// Terms proposed by one party of a stream and awaiting the other
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamAmendment {
+    pub proposer: Address,
+    pub terms: AmendmentTerms,
+    pub proposed_at: u64,
+    /// Funds a sender pays in with a proposal that raises the total, creation
+    /// fee included, returned if the proposal is rejected or replaced
+    pub escrowed: i128,
+}
+
+/// Max streams one shared deposit can fund, so liquidation stays within
+/// a single transaction's budget
+pub const MAX_DEPOSIT_STREAMS: u32 = 50;
+
+/// A sender's shared balance for one token, funding any number of
+/// `StreamRate::FromDeposit` streams. `balance` is what was left undrawn at
+/// `checkpoint_time`; since then the streams draw `outgoing_rate` per second.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SenderDeposit {
+    pub balance: i128,
+    pub outgoing_rate: i128,
+    pub checkpoint_time: u64,
+    /// Active streams funded from this deposit
+    pub streams: Vec<u64>,
+}
+
+/// Solvency of a sender's shared deposit as of the query
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositSolvency {
+    /// Undrawn balance
+    pub balance: i128,
+    /// Sum of the rates of every stream funded from the deposit
+    pub outgoing_rate: i128,
+    /// When the balance can no longer fund another second, `None` while
+    /// nothing is streaming
+    pub depletion_time: Option<u64>,
+    /// The deposit has run out and its streams can be liquidated
+    pub is_depleted: bool,
+}
+
+/// When a periodic stream next releases funds, and how much
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NextUnlock {
+    pub timestamp: u64,
+    pub amount: i128,
 }
 
 // Role definitions for RBAC
@@ -33,6 +176,23 @@ pub enum Role {
     ComplianceOfficer, // Can execute regulatory clawbacks
 }
 
+// When the protocol fee is taken from a stream
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FeeMode {
+    OnCreation = 0,   // Deducted from the deposit when the stream is created
+    OnWithdrawal = 1, // Deducted from every payout to the receiver
+}
+
+// Contract-wide circuit breaker, stored under DataKey::IsPaused
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseState {
+    pub creation: bool,   // Stream creation, top-ups, proposals and requests
+    pub withdrawal: bool, // Withdrawals and cancellation payouts
+    pub admin: bool,      // Receiver transfers and fee configuration
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct PriceOracle {
@@ -50,12 +210,71 @@ pub struct UsdPegConfig {
 }
 
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Milestone {
     pub timestamp: u64,
     pub percentage: u32,
 }
 
+/// Who may cancel a stream
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CancelAuthority {
+    Nobody = 0,
+    SenderOnly = 1,
+    /// The current receipt owner
+    ReceiverOnly = 2,
+    Both = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancellationPolicy {
+    pub authority: CancelAuthority,
+    /// If set, the stream can no longer be cancelled from this timestamp on
+    pub cancelable_until: Option<u64>,
+}
+
+/// Optional terms for `create_stream_with_options` and `create_proposal_with_config`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamOptions {
+    /// Defaults to `start_time` (no cliff)
+    pub cliff_time: Option<u64>,
+    pub milestones: Vec<Milestone>,
+    pub curve_type: CurveType,
+    pub is_soulbound: bool,
+    pub vault_address: Option<Address>,
+    pub interest_strategy: u32,
+    pub metadata: Option<BytesN<32>>,
+    pub cancellation: CancellationPolicy,
+    /// Share of the total (in bps) claimable at `start_time`, before any cliff
+    pub initial_unlock_bps: u32,
+    /// Can freeze the stream and settle disputes
+    pub arbiter: Option<Address>,
+}
+
+impl StreamOptions {
+    /// Linear stream with no cliff or milestones, cancellable by either party at any time
+    pub fn new(env: &Env) -> Self {
+        StreamOptions {
+            cliff_time: None,
+            milestones: Vec::new(env),
+            curve_type: CurveType::Linear,
+            is_soulbound: false,
+            vault_address: None,
+            interest_strategy: 0,
+            metadata: None,
+            cancellation: CancellationPolicy {
+                authority: CancelAuthority::Both,
+                cancelable_until: None,
+            },
+            initial_unlock_bps: 0,
+            arbiter: None,
+        }
+    }
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct Stream {
@@ -64,9 +283,15 @@ pub struct Stream {
     pub token: Address,
     pub total_amount: i128,
     pub start_time: u64,
+    /// Nothing is withdrawable before this time. Equal to `start_time` when
+    /// the stream has no cliff.
+    pub cliff_time: u64,
     pub end_time: u64,
     pub withdrawn: i128,
     pub withdrawn_amount: i128,
+    /// Fee taken from each payout, fixed at creation. Zero when the fee was
+    /// already charged on creation.
+    pub withdrawal_fee_bps: u32,
     pub cancelled: bool,
     pub receipt_owner: Address,
     pub is_paused: bool,
@@ -97,13 +322,62 @@ pub struct Stream {
     pub arbiter: Option<Address>,
     /// If true, stream is frozen pending dispute resolution
     pub is_frozen: bool,
+    pub cancellation: CancellationPolicy,
+    /// Share of `total_amount` (in bps) unlocked at `start_time`; the rest
+    /// follows the cliff and curve
+    pub initial_unlock_bps: u32,
+    /// Open-ended streams accrue at a fixed rate instead of following a
+    /// curve. `end_time` is then the time the deposited balance runs out,
+    /// or ran out while the stream is in debt.
+    pub rate: StreamRate,
+    /// Unlocked by earlier tranches before a fully unlocked stream was
+    /// topped up again. Part of `total_amount`, claimable from `start_time`.
+    pub carried_amount: i128,
 }
 
-// Legacy Stream struct (v1) - for migration example
-// This represents an older version without cliff_time
+/// Stream layout stored in the contract instance before streams moved to
+/// their own persistent entries. Read only by the storage migration.
+/// `curve_type` is kept as its raw discriminant.
 #[contracttype]
 #[derive(Clone)]
-pub struct StreamProposal {
+pub struct StreamV1 {
+    pub sender: Address,
+    pub receiver: Address,
+    pub token: Address,
+    pub total_amount: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub withdrawn: i128,
+    pub withdrawn_amount: i128,
+    pub cancelled: bool,
+    pub receipt_owner: Address,
+    pub is_paused: bool,
+    pub paused_time: u64,
+    pub total_paused_duration: u64,
+    pub milestones: Vec<Milestone>,
+    pub curve_type: u32,
+    pub interest_strategy: u32,
+    pub vault_address: Option<Address>,
+    pub deposited_principal: i128,
+    pub metadata: Option<BytesN<32>>,
+    pub is_usd_pegged: bool,
+    pub usd_amount: i128,
+    pub oracle_address: Address,
+    pub oracle_max_staleness: u64,
+    pub price_min: i128,
+    pub price_max: i128,
+    pub is_soulbound: bool,
+    pub clawback_enabled: bool,
+    pub arbiter: Option<Address>,
+    pub is_frozen: bool,
+}
+
+/// Proposal layout stored under `(PROPOSAL_COUNT, id)` before proposals
+/// gained stream options, rejection, councils, timelocks and escrow. Read
+/// only by the storage migration.
+#[contracttype]
+#[derive(Clone)]
+pub struct StreamProposalV1 {
     pub sender: Address,
     pub receiver: Address,
     pub token: Address,
@@ -116,6 +390,104 @@ pub struct StreamProposal {
     pub executed: bool,
 }
 
+/// Voting thresholds and lifetime of a multi-sig proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalConfig {
+    pub required_approvals: u32,
+    /// Rejections that discard the proposal. Must be 0 when nobody can
+    /// reject, i.e. without `voters` or a council.
+    pub required_rejections: u32,
+    /// Addresses allowed to vote. Empty lets anyone approve and nobody
+    /// reject; otherwise both thresholds must lie within the list's size.
+    pub voters: Vec<Address>,
+    pub deadline: u64,
+    /// Lock the funds when the proposal is created instead of on execution.
+    /// Required when a timelock applies.
+    pub escrow: bool,
+    /// Restrict voting to a council's members, weighted. The thresholds then
+    /// come from the council and the two counts above are ignored.
+    pub council_id: Option<u64>,
+    /// Delay between reaching quorum and execution; defaults to the global
+    /// proposal timelock
+    pub timelock: Option<u64>,
+}
+
+/// How long a queued proposal stays executable once its timelock has passed
+pub const PROPOSAL_EXECUTION_WINDOW: u64 = 7 * 24 * 60 * 60;
+
+/// Max members in one approver council
+pub const MAX_COUNCIL_MEMBERS: u32 = 20;
+
+/// How long a council membership change stays open for approval
+pub const COUNCIL_UPDATE_LIFETIME: u64 = 7 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CouncilMember {
+    pub member: Address,
+    pub weight: u32,
+}
+
+/// A stored set of weighted approvers that proposals reference by id
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Council {
+    pub members: Vec<CouncilMember>,
+    /// Combined weight needed to approve
+    pub threshold: u32,
+}
+
+/// New membership for a council, applied once the current members
+/// approving it reach the council's threshold
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CouncilUpdate {
+    pub proposer: Address,
+    pub members: Vec<CouncilMember>,
+    pub threshold: u32,
+    pub approvers: Vec<Address>,
+    /// Approvals close after this; another change can then be proposed
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamProposal {
+    pub sender: Address,
+    pub receiver: Address,
+    pub token: Address,
+    pub total_amount: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+    /// Terms of the stream created on execution
+    pub options: StreamOptions,
+    pub approvers: Vec<Address>,
+    pub required_approvals: u32,
+    pub rejectors: Vec<Address>,
+    pub required_rejections: u32,
+    /// Combined weight of the votes; each vote weighs 1 without a council
+    pub approval_weight: u32,
+    pub rejection_weight: u32,
+    /// Addresses allowed to vote without a council; empty lets anyone
+    /// approve and nobody reject
+    pub voters: Vec<Address>,
+    /// Council deciding the proposal. Its thresholds and vote weights are
+    /// recounted from the council's current membership on every vote.
+    pub council_id: Option<u64>,
+    pub deadline: u64,
+    pub timelock: u64,
+    /// Set once the proposal reaches quorum under a timelock
+    pub executable_at: Option<u64>,
+    /// Funds are held by the contract until execution or refund
+    pub escrowed: bool,
+}
+
+/// Max requests in one `create_streams_batch` call. Each stream writes about
+/// eight ledger entries of its own (stream, receipt and index slots), so
+/// larger batches would exceed the per-transaction write limits.
+pub const MAX_BATCH_SIZE: u32 = 5;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct StreamRequest {
@@ -138,23 +510,60 @@ pub struct InterestDistribution {
     pub total_interest: i128,
 }
 
+/// Which side of a stream an address index lists
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StreamIndex {
+    Sender = 0,
+    /// Current holder of the claim: the receiver, or the receipt owner once
+    /// receipts are transferable
+    Receiver = 1,
+    /// Address the stream's voting power is delegated to
+    Delegate = 2,
+}
+
+/// One page of an address index. `next_cursor` is where the following page
+/// starts, `None` once the end of the list has been read.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamPage {
+    pub stream_ids: Vec<u64>,
+    pub next_cursor: Option<u32>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
-    Stream(u64),
+    Stream(u64),  // Persistent: one entry per stream
+    Receipt(u64), // Persistent: one entry per stream receipt
     StreamId,
     Admin, // Kept for backward compatibility
     FeeBps,
     Treasury,
-    IsPaused,
+    IsPaused, // PauseState: contract-wide circuit breaker
     ReentrancyLock,
-    ContractVersion,        // Tracks current contract version
-    MigrationExecuted(u32), // Tracks which migrations have been executed
-    Role(Address, Role),    // RBAC: stores role assignments
-    SoulboundStreams,       // Vec<u64> of all soulbound stream IDs
-    ApprovedVaults,         // Vec<Address> of approved lending vaults
-    VaultShares(u64),       // Vault shares for stream_id
-    VotingDelegate(u64),    // Voting delegate for stream_id
+    ContractVersion,                       // Tracks current contract version
+    MigrationExecuted(u32),                // Tracks which migrations have been executed
+    Role(Address, Role),                   // RBAC: stores role assignments
+    SoulboundStreams,                      // Vec<u64> of all soulbound stream IDs
+    ApprovedVaults,                        // Vec<Address> of approved lending vaults
+    VaultShares(u64),                      // Persistent: vault shares for stream_id
+    VotingDelegate(u64),                   // Persistent: voting delegate for stream_id
+    FeeMode,                               // FeeMode: when protocol fees are charged
+    CollectedFees(Address),                // Total fees paid to treasury per token
+    StreamMigrationCursor,                 // Next legacy stream id to move out of instance storage
+    IndexLen(StreamIndex, Address),        // Persistent: number of slots an address list has used
+    IndexEntry(StreamIndex, Address, u32), // Persistent: stream id in a slot of an address list
+    IndexPos(StreamIndex, u64),            // Persistent: slot a stream occupies in its list
+    SenderDeposit(Address, Address),       // Persistent: shared deposit of (sender, token)
+    Amendment(u64),                        // Persistent: pending StreamAmendment of a stream
+    ProposalTimelock,                      // u64: default delay before proposals execute
+    Council(u64),                          // Persistent: approver council
+    CouncilUpdate(u64),                    // Persistent: pending membership change of a council
+    Proposal(u64),                         // Persistent: multi-sig StreamProposal
+    IndexCount(StreamIndex, Address),      // Persistent: number of streams listed for an address
+    AllowlistEnabled,                      // bool: only allowed tokens can be streamed
+    AllowedTokens,                         // Vec<Address> of tokens streams may use
 }
 
 #[contracttype]
@@ -183,6 +592,8 @@ pub struct StreamCreatedEvent {
 pub struct StreamClaimEvent {
     pub stream_id: u64,
     pub claimer: Address,
+    /// Address the tokens were sent to
+    pub to: Address,
     pub amount: i128,
     pub total_claimed: i128,
     pub timestamp: u64,
@@ -198,6 +609,16 @@ pub struct StreamCancelledEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FeeCollectedEvent {
+    pub stream_id: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub treasury: Address,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct ClawbackEvent {
@@ -227,6 +648,65 @@ pub struct DisputeResolvedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StreamRateUpdatedEvent {
+    pub stream_id: u64,
+    pub old_rate: i128,
+    pub new_rate: i128,
+    pub new_end_time: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StreamInsolventEvent {
+    pub stream_id: u64,
+    pub uncovered_debt: i128,
+    pub depleted_at: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StreamSolventEvent {
+    pub stream_id: u64,
+    pub new_end_time: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AmendmentProposedEvent {
+    pub stream_id: u64,
+    pub proposer: Address,
+    pub terms: AmendmentTerms,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StreamAmendedEvent {
+    pub stream_id: u64,
+    pub accepted_by: Address,
+    pub old_total: i128,
+    pub new_total: i128,
+    pub new_end_time: u64,
+    /// Unlocked at the moment of amendment, kept claimable as is
+    pub carried_amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DepositLiquidatedEvent {
+    pub sender: Address,
+    pub token: Address,
+    pub streams_stopped: u32,
+    pub depleted_at: u64,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct StreamToppedUpEvent {
@@ -235,6 +715,25 @@ pub struct StreamToppedUpEvent {
     pub amount: i128,
     pub new_total: i128,
     pub new_end_time: u64,
+    pub mode: TopUpMode,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PauseStateChangedEvent {
+    pub pauser: Address,
+    pub creation: bool,
+    pub withdrawal: bool,
+    pub admin: bool,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AddressRestrictedEvent {
+    pub address: Address,
+    pub restricted: bool,
     pub timestamp: u64,
 }
 
@@ -270,10 +769,91 @@ pub struct ProposalApprovedEvent {
     pub proposal_id: u64,
     pub approver: Address,
     pub approval_count: u32,
+    pub approval_weight: u32,
     pub required_approvals: u32,
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProposalRejectedEvent {
+    pub proposal_id: u64,
+    pub rejector: Address,
+    pub rejection_count: u32,
+    pub rejection_weight: u32,
+    pub required_rejections: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CouncilCreatedEvent {
+    pub council_id: u64,
+    pub creator: Address,
+    pub members: Vec<CouncilMember>,
+    pub threshold: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CouncilUpdateProposedEvent {
+    pub council_id: u64,
+    pub proposer: Address,
+    pub members: Vec<CouncilMember>,
+    pub threshold: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CouncilUpdateCancelledEvent {
+    pub council_id: u64,
+    pub proposer: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CouncilUpdatedEvent {
+    pub council_id: u64,
+    pub members: Vec<CouncilMember>,
+    pub threshold: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProposalQueuedEvent {
+    pub proposal_id: u64,
+    pub executable_at: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProposalVetoedEvent {
+    pub proposal_id: u64,
+    pub vetoed_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProposalCancelledEvent {
+    pub proposal_id: u64,
+    pub sender: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProposalPrunedEvent {
+    pub proposal_id: u64,
+    pub deadline: u64,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct ProposalCreatedEvent {
@@ -285,7 +865,11 @@ pub struct ProposalCreatedEvent {
     pub start_time: u64,
     pub end_time: u64,
     pub required_approvals: u32,
+    pub required_rejections: u32,
     pub deadline: u64,
+    pub escrowed: bool,
+    pub council_id: Option<u64>,
+    pub timelock: u64,
     pub timestamp: u64,
 }
 
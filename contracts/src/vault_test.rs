@@ -1,8 +1,8 @@
 #![cfg(test)]
-#![cfg(feature = "vault_tests")] // Disabled - requires admin initialization
+use crate::test_utils::create_token_contract;
 use crate::{StellarStreamContract, StellarStreamContractClient};
 use soroban_sdk::{
-    contract, contractimpl,
+    contract, contractimpl, symbol_short,
     testutils::{Address as _, Ledger, LedgerInfo},
     token::{StellarAssetClient, TokenClient},
     Address, Env, Vec,
@@ -14,13 +14,25 @@ pub struct MockVault;
 
 #[contractimpl]
 impl MockVault {
+    pub fn __constructor(env: Env, token: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("token"), &token);
+    }
+
     pub fn deposit(_env: Env, _from: Address, amount: i128) -> i128 {
         // Return 1:1 shares for simplicity
         amount
     }
 
-    pub fn withdraw(_env: Env, _to: Address, shares: i128) -> i128 {
+    pub fn withdraw(env: Env, to: Address, shares: i128) -> i128 {
         // Return 1:1 tokens for simplicity
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("token"))
+            .unwrap();
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &to, &shares);
         shares
     }
 
@@ -29,13 +41,6 @@ impl MockVault {
     }
 }
 
-fn create_token_contract<'a>(env: &Env, admin: &Address) -> (Address, TokenClient<'a>) {
-    let contract_id = env
-        .register_stellar_asset_contract_v2(admin.clone())
-        .address();
-    (contract_id.clone(), TokenClient::new(env, &contract_id))
-}
-
 #[test]
 fn test_approve_vault() {
     let env = Env::default();
@@ -44,6 +49,7 @@ fn test_approve_vault() {
     let admin = Address::generate(&env);
     let contract_id = env.register(StellarStreamContract, ());
     let client = StellarStreamContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
 
     let vault = Address::generate(&env);
 
@@ -62,6 +68,7 @@ fn test_revoke_vault() {
     let admin = Address::generate(&env);
     let contract_id = env.register(StellarStreamContract, ());
     let client = StellarStreamContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
 
     let vault = Address::generate(&env);
 
@@ -86,10 +93,11 @@ fn test_create_stream_with_vault() {
     let token_admin_client = StellarAssetClient::new(&env, &token_address);
 
     // Register vault
-    let vault_id = env.register(MockVault, ());
+    let vault_id = env.register(MockVault, (token_address.clone(),));
 
     let contract_id = env.register(StellarStreamContract, ());
     let client = StellarStreamContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
 
     // Mint tokens to sender
     token_admin_client.mint(&sender, &1000);
@@ -123,7 +131,7 @@ fn test_create_stream_with_vault() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_create_stream_with_unapproved_vault() {
     let env = Env::default();
     env.mock_all_auths();
@@ -136,10 +144,11 @@ fn test_create_stream_with_unapproved_vault() {
     let token_admin_client = StellarAssetClient::new(&env, &token_address);
 
     // Register vault but DON'T approve it
-    let vault_id = env.register(MockVault, ());
+    let vault_id = env.register(MockVault, (token_address.clone(),));
 
     let contract_id = env.register(StellarStreamContract, ());
     let client = StellarStreamContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
 
     token_admin_client.mint(&sender, &1000);
 
@@ -170,10 +179,11 @@ fn test_withdraw_from_vault_stream() {
 
     let (token_address, _token_client) = create_token_contract(&env, &admin);
     let token_admin_client = StellarAssetClient::new(&env, &token_address);
-    let vault_id = env.register(MockVault, ());
+    let vault_id = env.register(MockVault, (token_address.clone(),));
 
     let contract_id = env.register(StellarStreamContract, ());
     let client = StellarStreamContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
 
     token_admin_client.mint(&sender, &1000);
     client.approve_vault(&admin, &vault_id);
@@ -196,7 +206,7 @@ fn test_withdraw_from_vault_stream() {
     // Move time to 50% completion
     env.ledger().set(LedgerInfo {
         timestamp: 150,
-        protocol_version: 20,
+        protocol_version: 22,
         sequence_number: 10,
         network_id: Default::default(),
         base_reserve: 10,
@@ -225,10 +235,11 @@ fn test_cancel_stream_with_vault() {
 
     let (token_address, _token_client) = create_token_contract(&env, &admin);
     let token_admin_client = StellarAssetClient::new(&env, &token_address);
-    let vault_id = env.register(MockVault, ());
+    let vault_id = env.register(MockVault, (token_address.clone(),));
 
     let contract_id = env.register(StellarStreamContract, ());
     let client = StellarStreamContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
 
     token_admin_client.mint(&sender, &1000);
     client.approve_vault(&admin, &vault_id);
@@ -250,7 +261,7 @@ fn test_cancel_stream_with_vault() {
     // Move time to 30% completion
     env.ledger().set(LedgerInfo {
         timestamp: 130,
-        protocol_version: 20,
+        protocol_version: 22,
         sequence_number: 10,
         network_id: Default::default(),
         base_reserve: 10,
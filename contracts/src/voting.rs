@@ -2,6 +2,7 @@ use soroban_sdk::{Address, Env};
 
 /// Get voting power for a stream
 /// Returns the unlocked balance that can be used for voting
+#[allow(dead_code)]
 pub fn get_voting_power(_env: &Env, stream: &crate::types::Stream, current_time: u64) -> i128 {
     if stream.cancelled {
         return 0;
@@ -23,6 +24,7 @@ pub fn get_voting_power(_env: &Env, stream: &crate::types::Stream, current_time:
 }
 
 /// Get total stream balance (locked + unlocked)
+#[allow(dead_code)]
 pub fn get_total_balance(stream: &crate::types::Stream) -> i128 {
     if stream.cancelled {
         return 0;
@@ -31,12 +33,10 @@ pub fn get_total_balance(stream: &crate::types::Stream) -> i128 {
 }
 
 /// Check if address has delegation rights for a stream
+#[allow(dead_code)]
 pub fn can_delegate(env: &Env, stream_id: u64, caller: &Address) -> bool {
-    let receipt: crate::types::StreamReceipt = env
-        .storage()
-        .instance()
-        .get(&(crate::storage::RECEIPT, stream_id))
-        .unwrap_or_else(|| panic!("Stream not found"));
+    let receipt =
+        crate::storage::load_receipt(env, stream_id).unwrap_or_else(|| panic!("Stream not found"));
 
     receipt.owner == *caller
 }
@@ -1,18 +1,12 @@
 #![cfg(test)]
+use crate::test_utils::create_token_contract;
 use crate::{StellarStreamContract, StellarStreamContractClient};
 use soroban_sdk::{
     testutils::{Address as _, Ledger, LedgerInfo},
-    token::{StellarAssetClient, TokenClient},
+    token::StellarAssetClient,
     Address, Env,
 };
 
-fn create_token_contract<'a>(env: &Env, admin: &Address) -> (Address, TokenClient<'a>) {
-    let contract_id = env
-        .register_stellar_asset_contract_v2(admin.clone())
-        .address();
-    (contract_id.clone(), TokenClient::new(env, &contract_id))
-}
-
 #[test]
 fn test_get_voting_power() {
     let env = Env::default();
@@ -236,3 +230,47 @@ fn test_voting_power_after_withdrawal() {
     let power_after = client.get_voting_power(&stream_id);
     assert_eq!(power_after, 0);
 }
+
+#[test]
+fn test_delegation_ends_when_receipt_changes_hands() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let receiver = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let delegate = Address::generate(&env);
+
+    let (token_address, _token_client) = create_token_contract(&env, &admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_address);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(&env, &contract_id);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_address,
+        &1000,
+        &100,
+        &200,
+        &crate::types::CurveType::Linear,
+        &false,
+    );
+    client.delegate_voting_power(&stream_id, &receiver, &delegate);
+
+    env.ledger().with_mut(|li| li.timestamp = 150);
+    assert_eq!(client.get_delegated_voting_power(&delegate), 500);
+
+    client.transfer_receipt(&stream_id, &receiver, &buyer);
+
+    assert_eq!(client.get_voting_delegate(&stream_id), None);
+    assert_eq!(client.get_delegated_voting_power(&delegate), 0);
+    assert_eq!(
+        client.try_delegate_voting_power(&stream_id, &receiver, &delegate),
+        Err(Ok(crate::errors::Error::NotReceiptOwner))
+    );
+}
@@ -0,0 +1,154 @@
+#![cfg(test)]
+use crate::errors::Error;
+use crate::test_utils::create_token_contract;
+use crate::types::{CurveType, StreamClaimEvent};
+use crate::{storage, StellarStreamContract, StellarStreamContractClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, IntoVal,
+};
+
+fn setup_test(
+    env: &Env,
+) -> (
+    StellarStreamContractClient<'_>,
+    Address,
+    Address,
+    u64,
+    TokenClient<'_>,
+) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+    let receiver = Address::generate(env);
+
+    let (token_address, token_client) = create_token_contract(env, &admin);
+    StellarAssetClient::new(env, &token_address).mint(&sender, &10_000);
+
+    let contract_id = env.register(StellarStreamContract, ());
+    let client = StellarStreamContractClient::new(env, &contract_id);
+
+    let stream_id = client.create_stream(
+        &sender,
+        &receiver,
+        &token_address,
+        &1000,
+        &0,
+        &100,
+        &CurveType::Linear,
+        &false,
+    );
+
+    (client, sender, receiver, stream_id, token_client)
+}
+
+#[test]
+fn test_partial_withdrawal_to_another_address() {
+    let env = Env::default();
+    let (client, _, receiver, stream_id, token_client) = setup_test(&env);
+    let cold_wallet = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(
+        client.withdraw_amount(&stream_id, &receiver, &200, &cold_wallet),
+        200
+    );
+
+    let (contract, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(contract, client.address);
+    assert_eq!(
+        topics,
+        (symbol_short!("claim"), receiver.clone()).into_val(&env)
+    );
+    let event: StreamClaimEvent = data.into_val(&env);
+    assert_eq!(event.stream_id, stream_id);
+    assert_eq!(event.claimer, receiver);
+    assert_eq!(event.to, cold_wallet);
+    assert_eq!(event.amount, 200);
+    assert_eq!(event.total_claimed, 200);
+
+    assert_eq!(token_client.balance(&cold_wallet), 200);
+    assert_eq!(token_client.balance(&receiver), 0);
+    assert_eq!(client.get_stream(&stream_id).withdrawn_amount, 200);
+
+    // The rest of what has unlocked is still claimable
+    assert_eq!(client.withdraw(&stream_id, &receiver), 300);
+    assert_eq!(token_client.balance(&receiver), 300);
+}
+
+#[test]
+fn test_cannot_withdraw_more_than_available() {
+    let env = Env::default();
+    let (client, _, receiver, stream_id, _) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(
+        client.try_withdraw_amount(&stream_id, &receiver, &501, &receiver),
+        Err(Ok(Error::InsufficientBalance))
+    );
+    assert_eq!(
+        client.try_withdraw_amount(&stream_id, &receiver, &0, &receiver),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    client.withdraw_amount(&stream_id, &receiver, &500, &receiver);
+    assert_eq!(
+        client.try_withdraw_amount(&stream_id, &receiver, &1, &receiver),
+        Err(Ok(Error::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_only_receipt_owner_can_withdraw_amount() {
+    let env = Env::default();
+    let (client, sender, _, stream_id, _) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(
+        client.try_withdraw_amount(&stream_id, &sender, &100, &sender),
+        Err(Ok(Error::NotReceiptOwner))
+    );
+}
+
+#[test]
+fn test_withdraw_amount_respects_stream_state() {
+    let env = Env::default();
+    let (client, sender, receiver, stream_id, _) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    client.pause_stream(&stream_id, &sender);
+    assert_eq!(
+        client.try_withdraw_amount(&stream_id, &receiver, &100, &receiver),
+        Err(Ok(Error::StreamPaused))
+    );
+    client.unpause_stream(&stream_id, &sender);
+
+    env.as_contract(&client.address, || {
+        let mut stream = storage::load_stream(&env, stream_id).unwrap();
+        stream.is_frozen = true;
+        storage::save_stream(&env, stream_id, &stream);
+    });
+    assert_eq!(
+        client.try_withdraw_amount(&stream_id, &receiver, &100, &receiver),
+        Err(Ok(Error::StreamFrozen))
+    );
+    assert_eq!(
+        client.try_withdraw(&stream_id, &receiver),
+        Err(Ok(Error::StreamFrozen))
+    );
+
+    env.as_contract(&client.address, || {
+        let mut stream = storage::load_stream(&env, stream_id).unwrap();
+        stream.is_frozen = false;
+        storage::save_stream(&env, stream_id, &stream);
+    });
+    client.cancel(&stream_id, &sender);
+    assert_eq!(
+        client.try_withdraw_amount(&stream_id, &receiver, &100, &receiver),
+        Err(Ok(Error::AlreadyCancelled))
+    );
+}